@@ -0,0 +1,66 @@
+// Benchmarks for the drawing paths that the performance work touches.
+//
+// These need a terminal, same as the examples, so run them in a real one with
+// `cargo bench --features bench`. Curses shares the terminal with criterion's
+// report, so the screen gets messy while they run, but the summary at the end
+// is printed after curses has shut down.
+
+extern crate criterion;
+extern crate easycurses;
+
+use criterion::Criterion;
+use easycurses::*;
+
+/// Text that fills every row but the last, so that nothing scrolls.
+fn screen_text(easy: &mut EasyCurses) -> String {
+  let (row_count, col_count) = easy.get_row_col_count();
+  "0123456789".chars().cycle().take(((row_count - 1) * col_count) as usize).collect()
+}
+
+fn bench_print_long_string(c: &mut Criterion, easy: &mut EasyCurses) {
+  c.bench_function("print long string", |b| {
+    let text = screen_text(easy);
+    b.iter(|| {
+      easy.move_rc(0, 0);
+      easy.print(&text)
+    })
+  });
+}
+
+fn bench_restore_full_screen(c: &mut Criterion, easy: &mut EasyCurses) {
+  c.bench_function("restore full screen region", |b| {
+    let text = screen_text(easy);
+    easy.move_rc(0, 0);
+    easy.print(&text);
+    let (row_count, col_count) = easy.get_row_col_count();
+    let snapshot = easy.capture_region(Rect::new(0, 0, row_count, col_count));
+    b.iter(|| easy.restore_region(&snapshot))
+  });
+}
+
+fn bench_print_char_loop(c: &mut Criterion, easy: &mut EasyCurses) {
+  c.bench_function("print_char every cell", |b| {
+    let (row_count, col_count) = easy.get_row_col_count();
+    b.iter(|| {
+      for row in 0..row_count - 1 {
+        easy.move_rc(row, 0);
+        for _ in 0..col_count {
+          easy.print_char('x');
+        }
+      }
+    })
+  });
+}
+
+fn main() {
+  let mut criterion = Criterion::default().configure_from_args();
+  {
+    // Curses can only be set up once at a time, so every benchmark shares it.
+    let mut easy = EasyCurses::initialize_system().expect("couldn't initialize curses");
+    bench_print_long_string(&mut criterion, &mut easy);
+    bench_restore_full_screen(&mut criterion, &mut easy);
+    bench_print_char_loop(&mut criterion, &mut easy);
+  }
+  // Curses is shut down by now, so the summary prints normally.
+  criterion.final_summary();
+}
@@ -2,7 +2,6 @@ extern crate easycurses;
 
 use easycurses::*;
 use std::cmp::{max, min};
-use std::iter::repeat;
 use std::thread::sleep;
 use std::time::Duration;
 use std::time::Instant;
@@ -42,7 +41,7 @@ fn main() {
       }
     }
     // Compute what we'll display.
-    let output = repeat('#').take(position as usize).collect::<String>();
+    let output = "#".repeat(position as usize);
 
     // Sleep a bit if we need to. This actually sleeps a little longer than
     // just the right time because it doesn't account for the display time
@@ -54,8 +53,6 @@ fn main() {
     }
 
     // Display
-    easy.print("\n");
-    easy.print(&output);
-    easy.refresh();
+    easy.append_line(&output);
   }
 }
@@ -10,14 +10,13 @@ use easycurses::Color::*;
 use easycurses::*;
 
 fn main() {
-  // Initialize the system
-  let mut easy = EasyCurses::initialize_system().unwrap();
-
-  // don't show the cursor
-  easy.set_cursor_visibility(CursorVisibility::Invisible);
-
-  // don't echo the user's input
-  easy.set_echo(false);
+  // Initialize the system, without showing the cursor or echoing the user's
+  // input.
+  let mut easy = EasyCurses::builder()
+    .cursor_visibility(CursorVisibility::Invisible)
+    .echo(false)
+    .initialize()
+    .unwrap();
 
   // we'll print this in green text.
   easy.set_color_pair(colorpair!(Green on Black));
@@ -0,0 +1,39 @@
+// Times printing a long ASCII string with `print` against printing the same
+// string one character at a time with `print_char`.
+
+extern crate easycurses;
+
+use easycurses::*;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 200;
+
+fn time_rounds<F: FnMut(&mut EasyCurses)>(easy: &mut EasyCurses, mut draw: F) -> Duration {
+  let start = Instant::now();
+  for _ in 0..ROUNDS {
+    easy.move_rc(0, 0);
+    draw(easy);
+  }
+  start.elapsed()
+}
+
+fn main() {
+  let mut easy = EasyCurses::initialize_system().unwrap();
+  let (row_count, col_count) = easy.get_row_col_count();
+  // Fill all but the last row, so that nothing scrolls.
+  let text = "0123456789".chars().cycle().take(((row_count - 1) * col_count) as usize).collect::<String>();
+
+  let bulk = time_rounds(&mut easy, |easy| {
+    easy.print(&text);
+  });
+  let per_char = time_rounds(&mut easy, |easy| {
+    for c in text.chars() {
+      easy.print_char(c);
+    }
+  });
+
+  drop(easy);
+  println!("{} chars, {} rounds each", text.len(), ROUNDS);
+  println!("print:      {:?}", bulk);
+  println!("print_char: {:?}", per_char);
+}
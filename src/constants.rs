@@ -1,5 +1,10 @@
 //! Special constants for things like drawing ascii art.
 
+/// The "alternate character set" line drawing and symbol characters.
+///
+/// This covers every ACS character that pancurses provides. The classic ACS
+/// set only has single-line box pieces, so there are no double-line corners or
+/// edges here.
 #[allow(missing_docs)]
 pub mod acs {
   use pancurses::*;
@@ -133,4 +138,88 @@ pub mod acs {
   pub fn ssss() -> chtype {
     ACS_SSSS()
   }
+
+  /// An ACS character, kept apart from plain `chtype` values so that an
+  /// arbitrary integer can't be passed where a line drawing character is
+  /// expected. There's one constructor for each of the functions above, with
+  /// the same name.
+  ///
+  /// ```rust
+  /// use easycurses::constants::acs::{self, AcsChar};
+  /// assert_eq!(AcsChar::hline().raw(), acs::hline());
+  /// ```
+  #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+  pub struct AcsChar(chtype);
+
+  impl AcsChar {
+    /// The raw `chtype`, the same as the function of the same name gives.
+    pub fn raw(self) -> chtype {
+      self.0
+    }
+  }
+
+  impl ToChtype for AcsChar {
+    fn to_chtype(&self) -> chtype {
+      self.0
+    }
+  }
+
+  macro_rules! acs_char_constructors {
+    ($($name:ident: $doc:literal,)*) => {
+      impl AcsChar {
+        $(
+          #[doc = $doc]
+          pub fn $name() -> AcsChar {
+            AcsChar($name())
+          }
+        )*
+      }
+    };
+  }
+
+  acs_char_constructors!(
+    llcorner: "The lower left corner of a box.",
+    lrcorner: "The lower right corner of a box.",
+    ulcorner: "The upper left corner of a box.",
+    urcorner: "The upper right corner of a box.",
+    btee: "A tee pointing up, for the bottom edge of a box.",
+    hline: "A horizontal line.",
+    ltee: "A tee pointing right, for the left edge of a box.",
+    plus: "Crossing lines, where four boxes meet.",
+    rtee: "A tee pointing left, for the right edge of a box.",
+    ttee: "A tee pointing down, for the top edge of a box.",
+    vline: "A vertical line.",
+    s1: "A scan line at the very top of the cell.",
+    s9: "A scan line at the very bottom of the cell.",
+    bullet: "A bullet point.",
+    ckboard: "A checker board pattern.",
+    degree: "A degree symbol.",
+    diamond: "A diamond.",
+    plminus: "A plus or minus sign.",
+    block: "A solid block.",
+    board: "A board of squares.",
+    darrow: "An arrow pointing down.",
+    lantern: "A lantern symbol.",
+    larrow: "An arrow pointing left.",
+    rarrow: "An arrow pointing right.",
+    uarrow: "An arrow pointing up.",
+    s3: "A scan line a third of the way down the cell.",
+    s7: "A scan line two thirds of the way down the cell.",
+    gequal: "A greater than or equal sign.",
+    lequal: "A less than or equal sign.",
+    nequal: "A not equal sign.",
+    pi: "The greek letter pi.",
+    sterling: "A pound sterling sign.",
+    bbss: "Another name for `urcorner`.",
+    bssb: "Another name for `ulcorner`.",
+    sbbs: "Another name for `lrcorner`.",
+    sbss: "Another name for `rtee`.",
+    ssbb: "Another name for `llcorner`.",
+    ssbs: "Another name for `btee`.",
+    sssb: "Another name for `ltee`.",
+    bsbs: "Another name for `hline`.",
+    bsss: "Another name for `ttee`.",
+    sbsb: "Another name for `vline`.",
+    ssss: "Another name for `plus`.",
+  );
 }
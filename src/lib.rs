@@ -18,15 +18,25 @@
 //! all build modes, and you should too.
 
 extern crate pancurses;
+#[cfg(feature = "unicode")]
+extern crate unicode_segmentation;
 
 pub mod constants;
 
 pub use pancurses::Input;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::IsTerminal;
 use std::iter::Iterator;
+use std::ops::{Deref, DerefMut};
 use std::panic::*;
-use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
 
+use constants::acs::AcsChar;
 use pancurses::ToChtype;
 
 /// A handy macro to make describing color pairs read more like normal english.
@@ -53,7 +63,7 @@ macro_rules! colorpair {
 }
 
 #[allow(non_upper_case_globals)]
-static curses_is_on: AtomicBool = ATOMIC_BOOL_INIT;
+static curses_is_on: AtomicBool = AtomicBool::new(false);
 
 /// The three options you can pass to [`EasyCurses::set_cursor_visibility`].
 ///
@@ -82,6 +92,63 @@ impl Default for CursorVisibility {
   }
 }
 
+impl CursorVisibility {
+  /// The modes to attempt, in order, when this mode is wanted but might not
+  /// be supported. Each chain steps toward less conspicuous modes first, and
+  /// only tries a more conspicuous one when nothing quieter is available.
+  fn fallback_chain(self) -> &'static [CursorVisibility] {
+    use CursorVisibility::*;
+    match self {
+      HighlyVisible => &[HighlyVisible, Visible, Invisible],
+      Visible => &[Visible, Invisible, HighlyVisible],
+      Invisible => &[Invisible],
+    }
+  }
+}
+
+/// Walks the fallback chain for `desired`, giving back the first mode that
+/// `try_set` reports success for.
+fn first_supported_visibility<F: FnMut(CursorVisibility) -> bool>(desired: CursorVisibility, mut try_set: F) -> Option<CursorVisibility> {
+  desired.fallback_chain().iter().cloned().find(|&vis| try_set(vis))
+}
+
+#[cfg(test)]
+mod cursor_visibility_tests {
+  use super::*;
+  use CursorVisibility::*;
+
+  #[test]
+  fn test_first_supported_visibility_all_supported() {
+    for &vis in [Invisible, Visible, HighlyVisible].iter() {
+      assert_eq!(first_supported_visibility(vis, |_| true), Some(vis));
+    }
+  }
+
+  #[test]
+  fn test_first_supported_visibility_falls_back() {
+    let no_highly = |vis| vis != HighlyVisible;
+    assert_eq!(first_supported_visibility(HighlyVisible, no_highly), Some(Visible));
+    let only_invisible = |vis| vis == Invisible;
+    assert_eq!(first_supported_visibility(HighlyVisible, only_invisible), Some(Invisible));
+    assert_eq!(first_supported_visibility(Visible, only_invisible), Some(Invisible));
+    let only_highly = |vis| vis == HighlyVisible;
+    assert_eq!(first_supported_visibility(Visible, only_highly), Some(HighlyVisible));
+    let no_visible = |vis| vis != Visible;
+    assert_eq!(first_supported_visibility(Visible, no_visible), Some(Invisible));
+  }
+
+  #[test]
+  fn test_first_supported_visibility_none_supported() {
+    assert_eq!(first_supported_visibility(Invisible, |_| false), None);
+    let mut attempts = vec![];
+    first_supported_visibility(HighlyVisible, |vis| {
+      attempts.push(vis);
+      false
+    });
+    assert_eq!(attempts, vec![HighlyVisible, Visible, Invisible]);
+  }
+}
+
 /// The curses color constants.
 ///
 /// Curses supports eight different colors. Each character cell has one "color
@@ -110,6 +177,73 @@ impl Color {
     static colors: &[Color] = &[Black, Red, Green, Yellow, Blue, Magenta, Cyan, White];
     colors.iter().cloned()
   }
+
+  /// Finds the color closest to the given RGB value, by straight line
+  /// distance in RGB space. This lets a theme that's defined in RGB still look
+  /// about right on a terminal with only the eight basic colors.
+  ///
+  /// Each color is taken to be the "pure" version of itself, with every
+  /// component either 0 or 255:
+  ///
+  /// | Color   | RGB             |
+  /// |---------|-----------------|
+  /// | Black   | (0, 0, 0)       |
+  /// | Red     | (255, 0, 0)     |
+  /// | Green   | (0, 255, 0)     |
+  /// | Yellow  | (255, 255, 0)   |
+  /// | Blue    | (0, 0, 255)     |
+  /// | Magenta | (255, 0, 255)   |
+  /// | Cyan    | (0, 255, 255)   |
+  /// | White   | (255, 255, 255) |
+  ///
+  /// Exact ties go to whichever color comes first in `color_iterator`.
+  ///
+  /// ```rust
+  /// use easycurses::Color;
+  /// assert_eq!(Color::nearest(250, 10, 10), Color::Red);
+  /// assert_eq!(Color::nearest(255, 165, 0), Color::Yellow);
+  /// ```
+  pub fn nearest(r: u8, g: u8, b: u8) -> Color {
+    let distance = |color: Color| {
+      let (cr, cg, cb) = color_reference_rgb(color);
+      let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+      d(r, cr) + d(g, cg) + d(b, cb)
+    };
+    let mut best = Color::Black;
+    for color in Color::color_iterator() {
+      if distance(color) < distance(best) {
+        best = color;
+      }
+    }
+    best
+  }
+}
+
+/// The RGB value used to represent each color by `Color::nearest`.
+fn color_reference_rgb(color: Color) -> (u8, u8, u8) {
+  use Color::*;
+  match color {
+    Black => (0, 0, 0),
+    Red => (255, 0, 0),
+    Green => (0, 255, 0),
+    Yellow => (255, 255, 0),
+    Blue => (0, 0, 255),
+    Magenta => (255, 0, 255),
+    Cyan => (0, 255, 255),
+    White => (255, 255, 255),
+  }
+}
+
+impl fmt::Display for Color {
+  /// Shows the name of the color.
+  ///
+  /// ```rust
+  /// use easycurses::Color;
+  /// assert_eq!(format!("{}", Color::Magenta), "Magenta");
+  /// ```
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
 }
 
 /// Converts a `Color` to the `i16` associated with it.
@@ -129,7 +263,6 @@ fn color_to_i16(color: Color) -> i16 {
 
 /// Converts an `i16` to the `Color` associated with it. Fails if the input is
 /// outside the range 0 to 7 (inclusive).
-#[cfg(test)]
 fn i16_to_color(val: i16) -> Option<Color> {
   use Color::*;
   match val {
@@ -155,11 +288,30 @@ mod color_tests {
     let colors = [Black, Red, Green, Yellow, Blue, Magenta, Cyan, White];
     for &color in colors.iter() {
       if i16_to_color(color_to_i16(color)).unwrap() != color {
-        panic!(color);
+        panic!("{:?}", color);
       }
     }
   }
 
+  #[test]
+  fn test_nearest_pure_colors() {
+    for color in Color::color_iterator() {
+      let (r, g, b) = color_reference_rgb(color);
+      assert_eq!(Color::nearest(r, g, b), color);
+    }
+  }
+
+  #[test]
+  fn test_nearest_in_between_colors() {
+    use Color::*;
+    assert_eq!(Color::nearest(200, 30, 40), Red);
+    assert_eq!(Color::nearest(40, 40, 40), Black);
+    assert_eq!(Color::nearest(200, 200, 210), White);
+    assert_eq!(Color::nearest(20, 180, 200), Cyan);
+    assert_eq!(Color::nearest(120, 0, 120), Black);
+    assert_eq!(Color::nearest(140, 0, 140), Magenta);
+  }
+
   #[test]
   fn test_color_i32_matches_color_constants() {
     use Color::*;
@@ -201,521 +353,6367 @@ impl ColorPair {
   fn fgbg_pairid(fg: i16, bg: i16) -> i16 {
     1 + (8 * fg + bg)
   }
-}
 
-impl Default for ColorPair {
-  /// The "default" color pair is White text on a Black background.
+  /// Starts building a `ColorPair` one color at a time. Any color that isn't
+  /// given defaults to a white foreground or a black background.
   ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair};
+  /// let pair = ColorPair::builder().fg(Color::Green).bg(Color::Black).build();
+  /// assert_eq!(pair, ColorPair::new(Color::Green, Color::Black));
   /// ```
-  /// use easycurses::{Color,ColorPair};
-  /// assert_eq!(ColorPair::default(), ColorPair::new(Color::White,Color::Black));
+  pub fn builder() -> ColorPairBuilder {
+    ColorPairBuilder {
+      fg: Color::White,
+      bg: Color::Black,
+    }
+  }
+
+  /// The foreground color of the pair. Pairs that weren't made from two
+  /// `Color` values, such as the ones from `define_gradient`, give `None`.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair};
+  /// let pair = ColorPair::new(Color::Green, Color::Black);
+  /// assert_eq!(pair.foreground(), Some(Color::Green));
+  /// assert_eq!(pair.background(), Some(Color::Black));
   /// ```
-  fn default() -> Self {
-    Self::new(Color::White, Color::Black)
+  pub fn foreground(self) -> Option<Color> {
+    self.basic_colors().map(|(fg, _)| fg)
   }
-}
 
-/// The various input modes that you can set for the terminal.
-///
-/// Use this with `set_input_mode`.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-pub enum InputMode {
-  /// Line buffering (special character processing)
-  Cooked,
-  /// Characters visible immediately (special character processing)
-  Character,
-  /// Line buffering (no special processing)
-  RawCooked,
-  /// Characters visible immediately (no special processing)
-  RawCharacter,
-}
+  /// The background color of the pair. Pairs that weren't made from two
+  /// `Color` values, such as the ones from `define_gradient`, give `None`.
+  pub fn background(self) -> Option<Color> {
+    self.basic_colors().map(|(_, bg)| bg)
+  }
 
-/// The various timeouts that you can set for `get_input` to operate with.
-///
-/// Use this with the `set_input_timeout` method.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-pub enum TimeoutMode {
-  /// If no input is available, return `None`.
-  Immediate,
-  /// Wait up to this many milliseconds before returning `None`.
-  WaitUpTo(i32),
-  /// Block until input is given.
-  Never,
+  /// Inverts `fgbg_pairid`, giving back the foreground and background.
+  fn colors(self) -> (Color, Color) {
+    let fgi = (self.0 - 1) / 8;
+    let bgi = (self.0 - 1) % 8;
+    (
+      i16_to_color(fgi).expect("ColorPair ids are always 1 through 64"),
+      i16_to_color(bgi).expect("ColorPair ids are always 1 through 64"),
+    )
+  }
+
+  /// Like `colors`, but gives `None` for pairs that weren't made out of the
+  /// basic colors, such as gradient pairs.
+  fn basic_colors(self) -> Option<(Color, Color)> {
+    if (1..=64).contains(&self.0) {
+      Some(self.colors())
+    } else {
+      None
+    }
+  }
 }
 
-impl Default for TimeoutMode {
+impl fmt::Display for ColorPair {
+  /// Shows the pair the same way it reads in English, foreground first.
+  ///
   /// ```rust
-  /// use easycurses::TimeoutMode;
-  /// assert_eq!(TimeoutMode::default(), TimeoutMode::Never);
+  /// use easycurses::{Color, ColorPair};
+  /// assert_eq!(format!("{}", ColorPair::new(Color::Green, Color::Black)), "Green on Black");
   /// ```
-  fn default() -> Self {
-    TimeoutMode::Never
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.basic_colors() {
+      Some((fg, bg)) => write!(f, "{} on {}", fg, bg),
+      None => write!(f, "Custom pair {}", self.0),
+    }
   }
 }
 
-/// Converts a `pancurses::OK` value into `true`, and all other values into
-/// `false`.
-fn to_bool(curses_bool: i32) -> bool {
-  curses_bool == pancurses::OK
-}
-
-/// This is a handle to all your fun curses functionality.
-///
-/// `EasyCurses` will automatically restore the terminal when you drop it, so
-/// you don't need to worry about any manual cleanup. Automatic cleanup will
-/// happen even if your program panics and unwinds, but it **will not** happen
-/// if your program panics and aborts (obviously). So, don't abort the program
-/// while curses is active, or your terminal session will just be ruined.
-///
-/// Except in the case of [`is_color_terminal`], all `EasyCurses` methods that
-/// return a `bool` use it to indicate if the requested operation was successful
-/// or not. Unfortunately, the curses library doesn't provide any more info than
-/// that, so a `bool` is all you get.
+/// Builds a [`ColorPair`] one color at a time, see [`ColorPair::builder`].
 ///
-/// [`is_color_terminal`]: #method.is_color_terminal
-#[derive(Debug)]
-pub struct EasyCurses {
-  /// This is the inner pancurses `Window` that the `EasyCurses` type wraps
-  /// over.
-  ///
-  /// This is only intended to be used as a last resort, if you really want to
-  /// call something that's not here. Under normal circumstances you shouldn't
-  /// need to touch this field at all. It's not "unsafe" to use in the
-  /// rust/memory sense, but if you access this field and then cause a bug in
-  /// `EasyCurses`, well that's your own fault.
-  pub win: pancurses::Window,
-  color_support: bool,
-  /// Determines if the window will automatically resize itself when
-  /// `KeyResize` comes in through the input channel. Defaults to true. If you
-  /// disable this and then don't call resize yourself then `KeyResize` comes
-  /// in you'll have a bad time.
-  pub auto_resize: bool,
+/// [`ColorPair`]: struct.ColorPair.html
+/// [`ColorPair::builder`]: struct.ColorPair.html#method.builder
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ColorPairBuilder {
+  fg: Color,
+  bg: Color,
 }
 
-impl Drop for EasyCurses {
-  /// Dropping EasyCurses causes the
-  /// [endwin](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/endwin.html)
-  /// curses function to be called.
-  fn drop(&mut self) {
-    // We will assume that the initialization code is correctly never
-    // initializing curses twice, and thus we will assume that it's safe to
-    // call endwin and then store that curses is off once that's done. If we
-    // were paranoid we'd do another compare_and_swap, but that's slower for
-    // no reason (again, assuming that the initialization code is correct).
-    pancurses::endwin();
-    curses_is_on.store(false, Ordering::SeqCst);
+impl ColorPairBuilder {
+  /// Sets the foreground color.
+  pub fn fg(self, fg: Color) -> Self {
+    ColorPairBuilder { fg, ..self }
+  }
+
+  /// Sets the background color.
+  pub fn bg(self, bg: Color) -> Self {
+    ColorPairBuilder { bg, ..self }
+  }
+
+  /// Makes the `ColorPair`.
+  pub fn build(self) -> ColorPair {
+    ColorPair::new(self.fg, self.bg)
   }
 }
 
-impl EasyCurses {
-  /// Initializes the curses system so that you can begin using curses.
-  ///
-  /// The name is long to remind you of the seriousness of attempting to turn
-  /// on curses: If the C layer encounters an error while trying to initialize
-  /// the user's terminal into curses mode it will "helpfully" print an error
-  /// message and exit the process on its own. There's no way to prevent this
-  /// from happening at the Rust level.
-  ///
-  /// If the terminal supports colors, they are automatically activated and
-  /// `ColorPair` values are initialized for all color foreground and
-  /// background combinations.
-  ///
-  /// # Errors
-  ///
-  /// Curses must not be double-initialized. This is tracked by easycurses
-  /// with an `AtomicBool` being flipped on and off. If it is on when you call
-  /// this method you get `None` back instead.
-  pub fn initialize_system() -> Option<Self> {
-    // https://doc.rust-lang.org/std/sync/atomic/struct.AtomicBool.html#method.compare_and_swap
-    // This method call is goofy as hell but basically we try to turn
-    // `curses_is_on` to true and then we're told if we actually changed it
-    // or not. If we did that means it was off and it's safe to turn it on.
-    // If we didn't change it that means it was already on and we should
-    // back out.
-    if !curses_is_on.compare_and_swap(false, true, Ordering::SeqCst) {
-      let w = pancurses::initscr();
-      let color_support = if pancurses::has_colors() {
-        to_bool(pancurses::start_color())
-      } else {
-        false
-      };
-      if color_support {
-        let color_count = pancurses::COLORS();
-        let pair_count = pancurses::COLOR_PAIRS();
-        if color_count >= 8 && pair_count >= 8 * 8 {
-          for fg in Color::color_iterator() {
-            for bg in Color::color_iterator() {
-              let fgi: i16 = color_to_i16(fg);
-              let bgi: i16 = color_to_i16(bg);
-              let pair_id: i16 = ColorPair::fgbg_pairid(fgi, bgi);
-              debug_assert!(
-                fgi as i32 <= color_count,
-                "Curses reported {} color ids available, but {:?} has id {}",
-                color_count,
-                fg,
-                fgi
-              );
-              debug_assert!(
-                bgi as i32 <= color_count,
-                "Curses reported {} color ids available, but {:?} has id {}",
-                color_count,
-                bg,
-                bgi
-              );
-              debug_assert!(
-                pair_id as i32 <= pair_count,
-                "Curses reported {} colorpair ids available, but {:?} on {:?} would be id {}",
-                pair_count,
-                fg,
-                bg,
-                pair_id
-              );
-              pancurses::init_pair(pair_id, fgi, bgi);
-            }
-          }
-        }
+#[cfg(test)]
+mod color_pair_builder_tests {
+  use super::*;
+
+  #[test]
+  fn test_builder_matches_new() {
+    for fg in Color::color_iterator() {
+      for bg in Color::color_iterator() {
+        assert_eq!(ColorPair::builder().fg(fg).bg(bg).build(), ColorPair::new(fg, bg));
       }
-      Some(EasyCurses {
-        win: w,
-        color_support: color_support,
-        auto_resize: true,
-      })
-    } else {
-      None
     }
   }
 
-  /// On Win32 systems this allows you to set the title of the PDcurses
-  /// window. On other systems this does nothing at all.
-  pub fn set_title_win32(&mut self, title: &str) {
-    pancurses::set_title(title);
+  #[test]
+  fn test_builder_defaults() {
+    use Color::*;
+    assert_eq!(ColorPair::builder().build(), ColorPair::new(White, Black));
+    assert_eq!(ColorPair::builder().fg(Red).build(), ColorPair::new(Red, Black));
+    assert_eq!(ColorPair::builder().bg(Blue).build(), ColorPair::new(White, Blue));
   }
+}
 
-  /// Attempts to assign a new cursor visibility. If this is successful you
-  /// get a `Some` back with the old setting inside. If this fails you get a
-  /// `None` back. For more info see
-  /// [curs_set](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/curs_set.html)
-  pub fn set_cursor_visibility(&mut self, vis: CursorVisibility) -> Option<CursorVisibility> {
-    use CursorVisibility::*;
-    let result = pancurses::curs_set(match vis {
-      Invisible => 0,
-      Visible => 1,
-      HighlyVisible => 2,
-    });
-    match result {
-      0 => Some(Invisible),
-      1 => Some(Visible),
-      2 => Some(HighlyVisible),
-      _ => None,
-    }
+/// The color remap table that leaves every color as itself.
+const IDENTITY_REMAP: [Color; 8] = [
+  Color::Black,
+  Color::Red,
+  Color::Green,
+  Color::Yellow,
+  Color::Blue,
+  Color::Magenta,
+  Color::Cyan,
+  Color::White,
+];
+
+/// Translates both colors of a pair through a remap table, which is indexed
+/// by each color's `i16` value.
+fn remap_pair(remap: &[Color; 8], pair: ColorPair) -> ColorPair {
+  // Custom pairs (such as from `define_gradient`) aren't made of the basic
+  // colors, so there's nothing to remap.
+  match pair.basic_colors() {
+    Some((fg, bg)) => ColorPair::new(remap[color_to_i16(fg) as usize], remap[color_to_i16(bg) as usize]),
+    None => pair,
   }
+}
 
-  /// The terminal gets input from the user. Then it's sometimes buffered up. At
-  /// some point it's passed into the program's input buffer, and then
-  /// `get_input` gets things out of that buffer.
-  ///
-  /// * Character: Input is passed in 1 character at a time, but special
-  ///   characters (such as Ctrl+C and Ctrl+S) are automatically processed for
-  ///   you by the terminal.
+/// The bit that represents the given pair id within a mask of initialized
+/// pairs. Pair ids run from 1 to 64, so they all fit in a `u64`.
+fn pair_bit(pair_id: i16) -> u64 {
+  1 << (pair_id - 1)
+}
+
+/// Checks if a pair id is marked within a mask of initialized pairs.
+fn pair_is_initialized(initialized_pairs: u64, pair_id: i16) -> bool {
+  (1..=64).contains(&pair_id) && initialized_pairs & pair_bit(pair_id) != 0
+}
+
+#[cfg(test)]
+mod color_pair_tests {
+  use super::*;
+
+  #[test]
+  fn test_color_pair_colors_round_trip() {
+    for fg in Color::color_iterator() {
+      for bg in Color::color_iterator() {
+        assert_eq!(ColorPair::new(fg, bg).colors(), (fg, bg));
+        assert_eq!(ColorPair::new(fg, bg).basic_colors(), Some((fg, bg)));
+      }
+    }
+  }
+
+  #[test]
+  fn test_foreground_background_round_trip() {
+    for fg in Color::color_iterator() {
+      for bg in Color::color_iterator() {
+        let pair = ColorPair::new(fg, bg);
+        assert_eq!((pair.foreground(), pair.background()), (Some(fg), Some(bg)));
+      }
+    }
+    assert_eq!(ColorPair(GRADIENT_FIRST_PAIR).foreground(), None);
+    assert_eq!(ColorPair::default().background(), Some(Color::Black));
+  }
+
+  #[test]
+  fn test_remap_pair() {
+    use Color::*;
+    let pair = ColorPair::new(Red, Green);
+    assert_eq!(remap_pair(&IDENTITY_REMAP, pair), pair);
+    let mut remap = IDENTITY_REMAP;
+    remap[color_to_i16(Red) as usize] = Magenta;
+    remap[color_to_i16(Green) as usize] = Blue;
+    assert_eq!(remap_pair(&remap, pair), ColorPair::new(Magenta, Blue));
+    assert_eq!(remap_pair(&remap, pair).0, ColorPair::fgbg_pairid(5, 4));
+    assert_eq!(remap_pair(&remap, ColorPair(GRADIENT_FIRST_PAIR)), ColorPair(GRADIENT_FIRST_PAIR));
+    assert_eq!(ColorPair(GRADIENT_FIRST_PAIR).basic_colors(), None);
+    assert_eq!(format!("{}", ColorPair(GRADIENT_FIRST_PAIR)), "Custom pair 65");
+  }
+
+  #[test]
+  fn test_pair_is_initialized_with_full_palette() {
+    let all_pairs = !0;
+    for fg in Color::color_iterator() {
+      for bg in Color::color_iterator() {
+        assert!(pair_is_initialized(all_pairs, ColorPair::new(fg, bg).0));
+      }
+    }
+  }
+
+  #[test]
+  fn test_pair_is_initialized_with_reduced_palette() {
+    // Simulates a terminal with only 64 pairs, where pair id 64 (White on
+    // White) fails to initialize.
+    let mut reduced = 0;
+    for pair_id in 1..64 {
+      reduced |= pair_bit(pair_id);
+    }
+    assert!(pair_is_initialized(reduced, ColorPair::new(Color::White, Color::Black).0));
+    assert!(!pair_is_initialized(reduced, ColorPair::new(Color::White, Color::White).0));
+    assert!(!pair_is_initialized(0, ColorPair::default().0));
+    assert!(!pair_is_initialized(!0, 0));
+    assert!(!pair_is_initialized(!0, 65));
+  }
+}
+
+/// The first color id that `define_gradient` allocates, just past the eight
+/// basic colors.
+const GRADIENT_FIRST_COLOR: i16 = 8;
+
+/// The first pair id that `define_gradient` allocates, just past the pairs
+/// used by `ColorPair::new`.
+const GRADIENT_FIRST_PAIR: i16 = 65;
+
+/// Evenly interpolates `steps` colors from `from` to `to`, including both
+/// ends.
+fn gradient_colors(from: (u8, u8, u8), to: (u8, u8, u8), steps: usize) -> Vec<(u8, u8, u8)> {
+  let lerp = |a: u8, b: u8, i: usize| {
+    if steps < 2 {
+      a
+    } else {
+      let t = i as f64 / (steps - 1) as f64;
+      (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8
+    }
+  };
+  (0..steps)
+    .map(|i| (lerp(from.0, to.0, i), lerp(from.1, to.1, i), lerp(from.2, to.2, i)))
+    .collect()
+}
+
+/// Scales an 8-bit color component to the 0 to 1000 range used by curses.
+fn curses_color_component(component: u8) -> i16 {
+  (i32::from(component) * 1000 / 255) as i16
+}
+
+/// Checks if a terminal with the given number of colors and color pairs has
+/// room for a gradient with the given number of steps.
+fn gradient_fits(color_count: i32, pair_count: i32, steps: usize) -> bool {
+  let color_room = (color_count - i32::from(GRADIENT_FIRST_COLOR)).max(0) as usize;
+  let pair_room = (pair_count - i32::from(GRADIENT_FIRST_PAIR)).max(0) as usize;
+  steps <= color_room && steps <= pair_room
+}
+
+#[cfg(test)]
+mod gradient_tests {
+  use super::*;
+
+  #[test]
+  fn test_gradient_colors_endpoints() {
+    let steps = gradient_colors((0, 0, 0), (255, 100, 10), 5);
+    assert_eq!(steps.len(), 5);
+    assert_eq!(steps[0], (0, 0, 0));
+    assert_eq!(steps[2], (128, 50, 5));
+    assert_eq!(steps[4], (255, 100, 10));
+  }
+
+  #[test]
+  fn test_gradient_colors_descending_and_short() {
+    assert_eq!(gradient_colors((200, 0, 0), (100, 0, 0), 3), vec![(200, 0, 0), (150, 0, 0), (100, 0, 0)]);
+    assert_eq!(gradient_colors((1, 2, 3), (4, 5, 6), 1), vec![(1, 2, 3)]);
+    assert!(gradient_colors((1, 2, 3), (4, 5, 6), 0).is_empty());
+  }
+
+  #[test]
+  fn test_curses_color_component() {
+    assert_eq!(curses_color_component(0), 0);
+    assert_eq!(curses_color_component(255), 1000);
+  }
+
+  #[test]
+  fn test_gradient_fits() {
+    assert!(gradient_fits(256, 256, 16));
+    assert!(gradient_fits(24, 81, 16));
+    assert!(!gradient_fits(8, 64, 1));
+    assert!(!gradient_fits(256, 70, 16));
+    assert!(!gradient_fits(16, 256, 16));
+    assert!(gradient_fits(8, 64, 0));
+  }
+}
+
+/// Hands out curses pair ids to foreground and background combinations as
+/// they're needed, starting at 1 and remembering each one, for when pairs are
+/// set up lazily.
+#[derive(Debug, Default, Clone)]
+struct PairCache {
+  pairs: HashMap<(Color, Color), i16>,
+}
+
+impl PairCache {
+  /// The pair id of a combination, if it has one yet.
+  fn get(&self, fg: Color, bg: Color) -> Option<i16> {
+    self.pairs.get(&(fg, bg)).cloned()
+  }
+
+  /// The pair id of a combination, giving it the next free id if it doesn't
+  /// have one yet. Valid pair ids are 1 up to (but not including) `max_pairs`,
+  /// and once those are used up you get `None` for new combinations.
+  fn get_or_alloc(&mut self, fg: Color, bg: Color, max_pairs: i32) -> Option<i16> {
+    if let Some(pair_id) = self.get(fg, bg) {
+      return Some(pair_id);
+    }
+    let next_id = self.pairs.len() as i32 + 1;
+    if next_id >= max_pairs || next_id > i32::from(i16::MAX) {
+      return None;
+    }
+    self.pairs.insert((fg, bg), next_id as i16);
+    Some(next_id as i16)
+  }
+
+  /// Finds the combination that was given a pair id.
+  fn colors(&self, pair_id: i16) -> Option<(Color, Color)> {
+    self.pairs.iter().find(|&(_, &id)| id == pair_id).map(|(&colors, _)| colors)
+  }
+}
+
+#[cfg(test)]
+mod pair_cache_tests {
+  use super::*;
+  use Color::*;
+
+  #[test]
+  fn test_pair_cache_allocates_in_order() {
+    let mut cache = PairCache::default();
+    assert_eq!(cache.get(Red, Black), None);
+    assert_eq!(cache.get_or_alloc(Red, Black, 256), Some(1));
+    assert_eq!(cache.get_or_alloc(Green, Blue, 256), Some(2));
+    assert_eq!(cache.get_or_alloc(Red, Black, 256), Some(1));
+    assert_eq!(cache.get(Green, Blue), Some(2));
+    assert_eq!(cache.colors(2), Some((Green, Blue)));
+    assert_eq!(cache.colors(3), None);
+  }
+
+  #[test]
+  fn test_pair_cache_capacity() {
+    let mut cache = PairCache::default();
+    assert_eq!(cache.get_or_alloc(Red, Black, 3), Some(1));
+    assert_eq!(cache.get_or_alloc(Green, Black, 3), Some(2));
+    assert_eq!(cache.get_or_alloc(Blue, Black, 3), None);
+    // Already known combinations still work when the cache is full.
+    assert_eq!(cache.get_or_alloc(Red, Black, 3), Some(1));
+    assert_eq!(cache.get_or_alloc(Red, Black, 0), Some(1));
+  }
+
+  #[test]
+  fn test_pair_cache_exhausted() {
+    let mut cache = PairCache::default();
+    for (i, fg) in Color::color_iterator().enumerate() {
+      assert_eq!(cache.get_or_alloc(fg, Black, 9), Some(i as i16 + 1));
+    }
+    assert_eq!(cache.get_or_alloc(Red, Blue, 9), None);
+    assert_eq!(cache.get(Red, Blue), None);
+    // Eager ids mean something else once ids are handed out lazily, which is
+    // why they can't be used as a fallback.
+    assert_eq!(cache.colors(ColorPair::new(Black, Red).0), Some((Red, Black)));
+  }
+}
+
+impl Default for ColorPair {
+  /// The "default" color pair is White text on a Black background.
+  ///
+  /// ```
+  /// use easycurses::{Color,ColorPair};
+  /// assert_eq!(ColorPair::default(), ColorPair::new(Color::White,Color::Black));
+  /// ```
+  fn default() -> Self {
+    Self::new(Color::White, Color::Black)
+  }
+}
+
+/// One of the 256 colors of the extended palette that most modern terminals
+/// have. Indexes 0 through 7 are the same as the basic `Color` values, 8
+/// through 15 are their bright versions, 16 through 231 are a 6x6x6 color
+/// cube, and 232 through 255 are a gray ramp.
+///
+/// Use these in a [`ColorPair256`]. Keep in mind that `define_gradient`
+/// redefines the colors from index 8 upward.
+///
+/// [`ColorPair256`]: struct.ColorPair256.html
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Color256(pub u8);
+
+impl From<Color> for Color256 {
+  /// ```rust
+  /// use easycurses::{Color, Color256};
+  /// assert_eq!(Color256::from(Color::Blue), Color256(4));
+  /// ```
+  fn from(color: Color) -> Self {
+    Color256(color_to_i16(color) as u8)
+  }
+}
+
+/// A color pair made from the extended 256 color palette, see
+/// [`EasyCurses::set_color_pair_256`].
+///
+/// [`EasyCurses::set_color_pair_256`]: struct.EasyCurses.html#method.set_color_pair_256
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ColorPair256 {
+  /// The foreground color.
+  pub fg: Color256,
+  /// The background color.
+  pub bg: Color256,
+}
+
+impl ColorPair256 {
+  /// Creates a new `ColorPair256` given a foreground and background.
+  pub fn new(fg: Color256, bg: Color256) -> Self {
+    ColorPair256 { fg, bg }
+  }
+}
+
+/// The highest pair id plus one that a 256 color pair can use. Pair ids are
+/// stored in 8 bits of each character cell, so ids past 255 can't be drawn.
+const PAIR_256_LIMIT: i32 = 256;
+
+/// Hands out curses pair ids to 256 color pairs as they're needed. Ids are
+/// given out from the top down, to stay clear of the basic pairs (1 to 64)
+/// and the gradient pairs, which count up from 65. This also keeps track of
+/// how many gradient pairs are in use, so that the two ranges can't overlap.
+#[derive(Debug, Default, Clone)]
+struct Pair256Cache {
+  pairs: HashMap<ColorPair256, i16>,
+  gradient_pairs: i16,
+}
+
+impl Pair256Cache {
+  /// The pair id of a pair, giving it the next free id if it doesn't have one
+  /// yet. Ids count down from just below `max_pairs` (or `PAIR_256_LIMIT` if
+  /// that's lower), and once they'd reach the gradient pairs (or the basic
+  /// pairs, with no gradient) you get `None` for new pairs.
+  fn get_or_alloc(&mut self, pair: ColorPair256, max_pairs: i32) -> Option<i16> {
+    if let Some(&pair_id) = self.pairs.get(&pair) {
+      return Some(pair_id);
+    }
+    let next_id = max_pairs.min(PAIR_256_LIMIT) - 1 - self.pairs.len() as i32;
+    if next_id < i32::from(GRADIENT_FIRST_PAIR) + i32::from(self.gradient_pairs) {
+      return None;
+    }
+    self.pairs.insert(pair, next_id as i16);
+    Some(next_id as i16)
+  }
+
+  /// Reserves the pair ids for a gradient of `steps` pairs, counting up from
+  /// `GRADIENT_FIRST_PAIR`. Gives `false` if that would reach a pair id that's
+  /// already in use by a 256 color pair. The ids of an earlier, longer
+  /// gradient stay reserved, since its colors may still be on the screen.
+  fn reserve_gradient(&mut self, steps: usize) -> bool {
+    let gradient_end = i32::from(GRADIENT_FIRST_PAIR) + steps as i32;
+    let lowest_256 = self.pairs.values().min().map_or(i32::MAX, |&pair_id| i32::from(pair_id));
+    if gradient_end > lowest_256 {
+      return false;
+    }
+    self.gradient_pairs = self.gradient_pairs.max(steps as i16);
+    true
+  }
+}
+
+#[cfg(test)]
+mod pair_256_tests {
+  use super::*;
+
+  #[test]
+  fn test_pair_256_cache_allocates_down() {
+    let mut cache = Pair256Cache::default();
+    let a = ColorPair256::new(Color256(196), Color256(16));
+    let b = ColorPair256::new(Color256(46), Color256(232));
+    assert_eq!(cache.get_or_alloc(a, 32767), Some(255));
+    assert_eq!(cache.get_or_alloc(b, 32767), Some(254));
+    assert_eq!(cache.get_or_alloc(a, 32767), Some(255));
+  }
+
+  #[test]
+  fn test_pair_256_cache_capacity() {
+    let mut cache = Pair256Cache::default();
+    let top = i32::from(GRADIENT_FIRST_PAIR) + 2;
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(1), Color256(2)), top), Some(66));
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(3), Color256(4)), top), Some(65));
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(5), Color256(6)), top), None);
+  }
+
+  #[test]
+  fn test_pair_256_cache_stops_at_gradient() {
+    let mut cache = Pair256Cache::default();
+    let top = i32::from(GRADIENT_FIRST_PAIR) + 4;
+    assert!(cache.reserve_gradient(2));
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(1), Color256(2)), top), Some(68));
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(3), Color256(4)), top), Some(67));
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(5), Color256(6)), top), None);
+  }
+
+  #[test]
+  fn test_gradient_stops_at_pair_256() {
+    let mut cache = Pair256Cache::default();
+    let top = i32::from(GRADIENT_FIRST_PAIR) + 4;
+    assert_eq!(cache.get_or_alloc(ColorPair256::new(Color256(1), Color256(2)), top), Some(68));
+    assert!(!cache.reserve_gradient(4));
+    assert!(cache.reserve_gradient(3));
+    assert!(cache.reserve_gradient(1));
+    assert_eq!(cache.gradient_pairs, 3);
+  }
+}
+
+/// A rectangular area of the window, using `(R,C)` coordinates.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Rect {
+  /// The row of the top edge.
+  pub row: i32,
+  /// The column of the left edge.
+  pub col: i32,
+  /// How many rows the rectangle covers.
+  pub row_count: i32,
+  /// How many columns the rectangle covers.
+  pub col_count: i32,
+}
+
+impl Rect {
+  /// Creates a new `Rect` with its top left corner at the given row and
+  /// column, covering the given number of rows and columns.
+  pub fn new(row: i32, col: i32, row_count: i32, col_count: i32) -> Self {
+    Rect {
+      row,
+      col,
+      row_count,
+      col_count,
+    }
+  }
+
+  /// Checks if the given location is within the rectangle.
+  pub fn contains(&self, row: i32, col: i32) -> bool {
+    row >= self.row && row < self.row + self.row_count && col >= self.col && col < self.col + self.col_count
+  }
+
+  /// The part of this rectangle that's also within `other`. If they don't
+  /// overlap the result covers no rows or no columns. The math is done
+  /// wide, so huge rectangles can't overflow.
+  fn intersection(&self, other: Rect) -> Rect {
+    fn span(start: i32, count: i32, other_start: i32, other_count: i32) -> (i32, i32) {
+      let low = i64::from(start).max(i64::from(other_start));
+      let high = (i64::from(start) + i64::from(count)).min(i64::from(other_start) + i64::from(other_count));
+      (low.min(i64::from(i32::MAX)) as i32, (high - low).max(0) as i32)
+    }
+    let (row, row_count) = span(self.row, self.row_count, other.row, other.row_count);
+    let (col, col_count) = span(self.col, self.col_count, other.col, other.col_count);
+    Rect::new(row, col, row_count, col_count)
+  }
+
+  /// All of the `(row, col)` locations along the edge of the rectangle, each
+  /// given once.
+  fn perimeter(&self) -> Vec<(i32, i32)> {
+    if self.row_count <= 0 || self.col_count <= 0 {
+      return Vec::new();
+    }
+    let bottom = self.row + self.row_count - 1;
+    let right = self.col + self.col_count - 1;
+    let mut cells = Vec::new();
+    for col in self.col..=right {
+      cells.push((self.row, col));
+      if bottom != self.row {
+        cells.push((bottom, col));
+      }
+    }
+    for row in self.row + 1..bottom {
+      cells.push((row, self.col));
+      if right != self.col {
+        cells.push((row, right));
+      }
+    }
+    cells
+  }
+}
+
+/// Splits `total` into `parts` pieces as evenly as possible, giving back the
+/// `(offset, length)` of each piece. Leftovers go to the earliest pieces.
+fn split_evenly(total: i32, parts: usize) -> Vec<(i32, i32)> {
+  let base = total / parts as i32;
+  let leftover = total % parts as i32;
+  let mut offset = 0;
+  (0..parts as i32)
+    .map(|i| {
+      let length = base + if i < leftover { 1 } else { 0 };
+      let piece = (offset, length);
+      offset += length;
+      piece
+    })
+    .collect()
+}
+
+/// Divides `area` into a `rows` by `cols` grid, in row-major order.
+fn grid_rects(area: Rect, rows: usize, cols: usize) -> Vec<Rect> {
+  if rows == 0 || cols == 0 {
+    return Vec::new();
+  }
+  let col_pieces = split_evenly(area.col_count, cols);
+  split_evenly(area.row_count, rows)
+    .into_iter()
+    .flat_map(|(row_offset, row_count)| {
+      col_pieces
+        .iter()
+        .map(move |&(col_offset, col_count)| Rect::new(area.row + row_offset, area.col + col_offset, row_count, col_count))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod rect_tests {
+  use super::*;
+
+  #[test]
+  fn test_intersection() {
+    let window = Rect::new(0, 0, 24, 80);
+    assert_eq!(Rect::new(-2, 70, 5, 20).intersection(window), Rect::new(0, 70, 3, 10));
+    assert_eq!(Rect::new(1, 1, i32::MAX, i32::MAX).intersection(window), Rect::new(1, 1, 23, 79));
+    let outside = Rect::new(i32::MAX, 0, i32::MAX, 5).intersection(window);
+    assert_eq!(outside.row_count, 0);
+  }
+
+  #[test]
+  fn test_grid_rects_tile_the_area() {
+    let area = Rect::new(0, 0, 25, 81);
+    let rects = grid_rects(area, 3, 4);
+    assert_eq!(rects.len(), 12);
+    for row in 0..area.row_count {
+      for col in 0..area.col_count {
+        let covering = rects.iter().filter(|rect| rect.contains(row, col)).count();
+        assert_eq!(covering, 1, "cell ({}, {}) is covered {} times", row, col, covering);
+      }
+    }
+  }
+
+  #[test]
+  fn test_grid_rects_spread_leftovers() {
+    let rects = grid_rects(Rect::new(0, 0, 10, 10), 1, 3);
+    assert_eq!(rects, vec![Rect::new(0, 0, 10, 4), Rect::new(0, 4, 10, 3), Rect::new(0, 7, 10, 3)]);
+  }
+
+  #[test]
+  fn test_rect_perimeter() {
+    let rect = Rect::new(1, 1, 3, 4);
+    let cells = rect.perimeter();
+    assert_eq!(cells.len(), 10);
+    assert!(!cells.contains(&(2, 2)));
+    assert!(!cells.contains(&(2, 3)));
+    assert_eq!(Rect::new(0, 0, 1, 1).perimeter(), vec![(0, 0)]);
+    assert_eq!(Rect::new(0, 0, 1, 3).perimeter().len(), 3);
+    assert!(Rect::new(0, 0, 0, 3).perimeter().is_empty());
+  }
+
+  #[test]
+  fn test_grid_rects_empty_grid() {
+    assert!(grid_rects(Rect::new(0, 0, 10, 10), 0, 3).is_empty());
+    assert!(grid_rects(Rect::new(0, 0, 10, 10), 3, 0).is_empty());
+  }
+}
+
+/// The offset of the visible window within a larger "world" coordinate space,
+/// for when what you're drawing is bigger than the screen. World position
+/// `(offset_row, offset_col)` shows up at the top left of the window.
+///
+/// See [`EasyCurses::print_world`].
+///
+/// [`EasyCurses::print_world`]: struct.EasyCurses.html#method.print_world
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Viewport {
+  /// The world row shown at the top of the window.
+  pub offset_row: i32,
+  /// The world column shown at the left of the window.
+  pub offset_col: i32,
+}
+
+impl Viewport {
+  /// Converts a world position to a window `(R,C)` position. The result might
+  /// be outside of the window.
+  ///
+  /// ```rust
+  /// use easycurses::Viewport;
+  /// let viewport = Viewport { offset_row: 10, offset_col: 20 };
+  /// assert_eq!(viewport.to_screen(12, 25), (2, 5));
+  /// assert_eq!(viewport.to_screen(0, 0), (-10, -20));
+  /// assert_eq!(viewport.to_screen(i32::MIN, 0), (i32::MIN, -20));
+  /// ```
+  pub fn to_screen(&self, world_row: i32, world_col: i32) -> (i32, i32) {
+    (world_row.saturating_sub(self.offset_row), world_col.saturating_sub(self.offset_col))
+  }
+}
+
+/// Works out which part of a line of `len` chars starting at `col` fits in a
+/// row `col_count` wide. Gives back how many chars to skip from the start of
+/// the text, how many to draw, and the column to start drawing at.
+fn clip_span(col: i32, len: i32, col_count: i32) -> (i32, i32, i32) {
+  let skip = (-col).max(0).min(len);
+  let start = col + skip;
+  let take = (len - skip).min(col_count - start).max(0);
+  (skip, take, start)
+}
+
+#[cfg(test)]
+mod viewport_tests {
+  use super::*;
+
+  #[test]
+  fn test_clip_span_fully_visible() {
+    assert_eq!(clip_span(2, 3, 10), (0, 3, 2));
+  }
+
+  #[test]
+  fn test_clip_span_left_and_right() {
+    assert_eq!(clip_span(-2, 5, 10), (2, 3, 0));
+    assert_eq!(clip_span(8, 5, 10), (0, 2, 8));
+    assert_eq!(clip_span(-2, 20, 10), (2, 10, 0));
+  }
+
+  #[test]
+  fn test_clip_span_off_screen() {
+    assert_eq!(clip_span(-5, 3, 10).1, 0);
+    assert_eq!(clip_span(12, 3, 10).1, 0);
+  }
+}
+
+/// The line styles that boxes can be drawn with, see
+/// [`EasyCurses::draw_box_in`].
+///
+/// The curses line drawing set only has single lines with square corners, so
+/// there are no double line or rounded styles.
+///
+/// [`EasyCurses::draw_box_in`]: struct.EasyCurses.html#method.draw_box_in
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum BorderStyle {
+  /// Single lines, from the line drawing characters.
+  Single,
+  /// Plain `+`, `-`, and `|` characters, which work on any terminal.
+  Ascii,
+}
+
+impl Default for BorderStyle {
+  /// ```rust
+  /// use easycurses::BorderStyle;
+  /// assert_eq!(BorderStyle::default(), BorderStyle::Single);
+  /// ```
+  fn default() -> Self {
+    BorderStyle::Single
+  }
+}
+
+impl BorderStyle {
+  /// The upper left, upper right, lower left, and lower right corners,
+  /// followed by the horizontal and vertical edges.
+  fn pieces(self) -> [pancurses::chtype; 6] {
+    use constants::acs;
+    match self {
+      BorderStyle::Ascii => {
+        let (corner, hline, vline) = ('+' as pancurses::chtype, '-' as pancurses::chtype, '|' as pancurses::chtype);
+        [corner, corner, corner, corner, hline, vline]
+      }
+      BorderStyle::Single => [
+        acs::ulcorner(),
+        acs::urcorner(),
+        acs::llcorner(),
+        acs::lrcorner(),
+        acs::hline(),
+        acs::vline(),
+      ],
+    }
+  }
+}
+
+/// Code points that take up no columns of their own: the Unicode "Combining
+/// Diacritical Marks" blocks (including the Extended, Supplement, and "for
+/// Symbols" blocks and the Combining Half Marks), the variation selectors, and
+/// the zero width spaces, joiners, and direction marks from General
+/// Punctuation, plus the zero width no-break space. This covers decomposed
+/// Latin, Greek, and Cyrillic text, but not the marks of every script.
+const ZERO_WIDTH_RANGES: [(char, char); 9] = [
+  ('\u{0300}', '\u{036F}'),
+  ('\u{1AB0}', '\u{1AFF}'),
+  ('\u{1DC0}', '\u{1DFF}'),
+  ('\u{200B}', '\u{200F}'),
+  ('\u{2060}', '\u{2064}'),
+  ('\u{20D0}', '\u{20FF}'),
+  ('\u{FE00}', '\u{FE0F}'),
+  ('\u{FE20}', '\u{FE2F}'),
+  ('\u{FEFF}', '\u{FEFF}'),
+];
+
+/// The number of columns a character takes up, either 0 or 1.
+fn char_width(c: char) -> i32 {
+  if ZERO_WIDTH_RANGES.iter().any(|&(low, high)| c >= low && c <= high) {
+    0
+  } else {
+    1
+  }
+}
+
+/// The number of columns that some text takes up, where combining marks and
+/// other zero width code points count as 0 and every other character counts
+/// as 1. This is what `print_field` and `print_centered` use to line text up,
+/// so that decomposed text such as `"e\u{301}"` (e with an acute accent)
+/// counts as one column.
+///
+/// With the `unicode` feature turned on, text is split into grapheme clusters
+/// (what a reader would call single characters) instead, and each cluster
+/// counts as 1. That keeps sequences such as flags and emoji with skin tone
+/// modifiers, which are made of several code points that aren't zero width on
+/// their own, together as one unit. Cutting text to fit, such as in
+/// `print_field`, then also never splits a cluster.
+///
+/// Curses itself doesn't know about clusters though, and still gives each of
+/// those code points a cell of its own. So a flag measures as 1 column but
+/// takes 2 on screen, and text with such clusters in it can take up more
+/// room than this says. Drawing that's clipped to a width, such as
+/// `print_field`, leaves out any cluster that doesn't fully fit rather than
+/// overflowing.
+///
+/// ```rust
+/// assert_eq!(easycurses::measure_text("abc"), 3);
+/// assert_eq!(easycurses::measure_text("e\u{301}"), 1);
+/// ```
+pub fn measure_text(text: &str) -> i32 {
+  text_units(text).iter().map(|&(_, width)| width).sum()
+}
+
+/// Splits text into the units that take up a column each (or none, for zero
+/// width characters), giving each unit along with its width. Without the
+/// `unicode` feature each unit is a single character.
+#[cfg(not(feature = "unicode"))]
+fn text_units(text: &str) -> Vec<(&str, i32)> {
+  text.char_indices().map(|(i, c)| (&text[i..i + c.len_utf8()], char_width(c))).collect()
+}
+
+/// Splits text into the units that take up a column each (or none, for zero
+/// width characters), giving each unit along with its width. With the
+/// `unicode` feature each unit is a grapheme cluster.
+#[cfg(feature = "unicode")]
+fn text_units(text: &str) -> Vec<(&str, i32)> {
+  use unicode_segmentation::UnicodeSegmentation;
+  text
+    .graphemes(true)
+    .map(|cluster| (cluster, if cluster.chars().any(|c| char_width(c) > 0) { 1 } else { 0 }))
+    .collect()
+}
+
+#[cfg(test)]
+mod text_width_tests {
+  use super::*;
+
+  #[test]
+  fn test_measure_text_combining() {
+    assert_eq!(measure_text(""), 0);
+    assert_eq!(measure_text("e\u{301}"), 1);
+    assert_eq!(measure_text("a\u{200D}b\u{FE0F}"), 2);
+    assert_eq!(measure_text("naïve"), 5);
+  }
+
+  #[cfg(not(feature = "unicode"))]
+  #[test]
+  fn test_measure_text_flag_code_points() {
+    assert_eq!(measure_text("\u{1F1EF}\u{1F1F5}"), 2);
+  }
+
+  #[cfg(feature = "unicode")]
+  #[test]
+  fn test_measure_text_flag_cluster() {
+    let flag = "\u{1F1EF}\u{1F1F5}";
+    assert_eq!(measure_text(flag), 1);
+    assert_eq!(measure_text(&format!("a{}b", flag)), 3);
+    assert_eq!(fit_field(&format!("{}{}", flag, flag), 1, Alignment::Left), flag);
+  }
+}
+
+/// Where text goes within a field that's wider than it, see
+/// [`EasyCurses::print_field`].
+///
+/// [`EasyCurses::print_field`]: struct.EasyCurses.html#method.print_field
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Alignment {
+  /// Against the left edge.
+  Left,
+  /// In the middle, with any odd extra space on the right.
+  Center,
+  /// Against the right edge.
+  Right,
+}
+
+/// Pads or cuts `text` to exactly `width` columns (see `measure_text`). Text
+/// that's too long keeps its start no matter the alignment.
+fn fit_field(text: &str, width: i32, align: Alignment) -> String {
+  let width = width.max(0);
+  let len = measure_text(text);
+  if len >= width {
+    let mut used = 0;
+    return text_units(text)
+      .into_iter()
+      .take_while(|&(_, unit_width)| {
+        used += unit_width;
+        used <= width
+      })
+      .map(|(unit, _)| unit)
+      .collect();
+  }
+  let extra = (width - len) as usize;
+  let before = match align {
+    Alignment::Left => 0,
+    Alignment::Center => extra / 2,
+    Alignment::Right => extra,
+  };
+  let mut field = " ".repeat(before);
+  field.push_str(text);
+  field.push_str(&" ".repeat(extra - before));
+  field
+}
+
+#[cfg(test)]
+mod field_tests {
+  use super::*;
+
+  #[test]
+  fn test_fit_field_short() {
+    assert_eq!(fit_field("ab", 6, Alignment::Left), "ab    ");
+    assert_eq!(fit_field("ab", 6, Alignment::Center), "  ab  ");
+    assert_eq!(fit_field("ab", 5, Alignment::Center), " ab  ");
+    assert_eq!(fit_field("ab", 6, Alignment::Right), "    ab");
+  }
+
+  #[test]
+  fn test_fit_field_overlong() {
+    assert_eq!(fit_field("abcdef", 4, Alignment::Left), "abcd");
+    assert_eq!(fit_field("abcdef", 4, Alignment::Center), "abcd");
+    assert_eq!(fit_field("abcdef", 4, Alignment::Right), "abcd");
+    assert_eq!(fit_field("abc", 0, Alignment::Left), "");
+    assert_eq!(fit_field("abc", -2, Alignment::Right), "");
+  }
+
+  #[test]
+  fn test_fit_field_combining() {
+    assert_eq!(fit_field("e\u{301}", 3, Alignment::Center), " e\u{301} ");
+    assert_eq!(fit_field("ae\u{301}x", 2, Alignment::Left), "ae\u{301}");
+  }
+}
+
+/// The four directions that the cursor can move in.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Direction {
+  /// Toward the top of the window.
+  Up,
+  /// Toward the bottom of the window.
+  Down,
+  /// Toward the left edge of the window.
+  Left,
+  /// Toward the right edge of the window.
+  Right,
+}
+
+impl Direction {
+  /// The `(row, col)` change of a single step in this direction.
+  ///
+  /// ```rust
+  /// use easycurses::Direction;
+  /// assert_eq!(Direction::Up.delta(), (-1, 0));
+  /// assert_eq!(Direction::Right.delta(), (0, 1));
+  /// ```
+  pub fn delta(self) -> (i32, i32) {
+    match self {
+      Direction::Up => (-1, 0),
+      Direction::Down => (1, 0),
+      Direction::Left => (0, -1),
+      Direction::Right => (0, 1),
+    }
+  }
+}
+
+/// Takes `steps` steps from `(row, col)` in the given direction, stopping at
+/// the edges of a window of the given size.
+fn step_within(row: i32, col: i32, dir: Direction, steps: i32, row_count: i32, col_count: i32) -> (i32, i32) {
+  let (d_row, d_col) = dir.delta();
+  let clamp = |val: i32, count: i32| val.max(0).min(count - 1);
+  let step = |val: i32, delta: i32| val.saturating_add(delta.saturating_mul(steps));
+  (clamp(step(row, d_row), row_count), clamp(step(col, d_col), col_count))
+}
+
+#[cfg(test)]
+mod direction_tests {
+  use super::*;
+
+  #[test]
+  fn test_step_within_each_direction() {
+    assert_eq!(step_within(5, 5, Direction::Up, 2, 11, 11), (3, 5));
+    assert_eq!(step_within(5, 5, Direction::Down, 2, 11, 11), (7, 5));
+    assert_eq!(step_within(5, 5, Direction::Left, 2, 11, 11), (5, 3));
+    assert_eq!(step_within(5, 5, Direction::Right, 2, 11, 11), (5, 7));
+  }
+
+  #[test]
+  fn test_step_within_clamps() {
+    assert_eq!(step_within(5, 5, Direction::Up, 100, 11, 11), (0, 5));
+    assert_eq!(step_within(5, 5, Direction::Down, 100, 11, 11), (10, 5));
+    assert_eq!(step_within(5, 5, Direction::Left, 100, 11, 11), (5, 0));
+    assert_eq!(step_within(5, 5, Direction::Right, 100, 11, 11), (5, 10));
+  }
+
+  #[test]
+  fn test_step_within_huge_steps() {
+    assert_eq!(step_within(5, 5, Direction::Down, i32::MAX, 11, 11), (10, 5));
+    assert_eq!(step_within(5, 5, Direction::Up, i32::MAX, 11, 11), (0, 5));
+    assert_eq!(step_within(5, 5, Direction::Right, i32::MAX, 11, 11), (5, 10));
+    assert_eq!(step_within(5, 5, Direction::Down, i32::MIN, 11, 11), (0, 5));
+    assert_eq!(step_within(5, 5, Direction::Up, i32::MIN, 11, 11), (10, 5));
+    assert_eq!(step_within(5, 5, Direction::Left, i32::MIN, 11, 11), (5, 10));
+  }
+}
+
+/// An input event, simplified from the raw curses [`Input`] values.
+///
+/// Get these from [`EasyCurses::get_event`]. Since they can also be built
+/// with `From<char>`, `From<Direction>`, and `From<Input>`, input handling code
+/// that works with events can be tested without a terminal:
+///
+/// ```rust
+/// use easycurses::{Direction, Event};
+///
+/// fn handle(event: Event, position: &mut i32) -> bool {
+///     match event {
+///         Event::Direction(Direction::Left) => *position -= 1,
+///         Event::Direction(Direction::Right) => *position += 1,
+///         Event::Character('q') => return false,
+///         _ => {}
+///     }
+///     true
+/// }
+///
+/// let mut position = 0;
+/// assert!(handle(Event::from(Direction::Right), &mut position));
+/// assert!(handle(Direction::Right.into(), &mut position));
+/// assert!(handle(Direction::Left.into(), &mut position));
+/// assert_eq!(position, 1);
+/// assert!(!handle('q'.into(), &mut position));
+/// ```
+///
+/// Since 0.13, every Enter input (`'\n'`, `'\r'`, and `KeyEnter`) becomes
+/// `Event::Enter` instead of `Event::Character('\n')` and friends, and mouse
+/// input from `get_event` becomes `Event::Mouse` instead of
+/// `Event::Other(Input::KeyMouse)`.
+/// Code matching on the old forms needs to be updated.
+///
+/// [`Input`]: enum.Input.html
+/// [`EasyCurses::get_event`]: struct.EasyCurses.html#method.get_event
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Event {
+  /// A plain character was typed.
+  Character(char),
+  /// One of the arrow keys was pressed.
+  Direction(Direction),
+  /// Enter (or Return) was pressed, see [`is_enter`].
+  ///
+  /// [`is_enter`]: fn.is_enter.html
+  Enter,
+  /// The terminal was resized.
+  Resize,
+  /// Something happened with the mouse, see
+  /// [`EasyCurses::enable_mouse`].
+  ///
+  /// [`EasyCurses::enable_mouse`]: struct.EasyCurses.html#method.enable_mouse
+  Mouse(MouseEvent),
+  /// Any other input, exactly as curses gave it.
+  Other(Input),
+}
+
+impl From<char> for Event {
+  fn from(c: char) -> Self {
+    Event::from(Input::Character(c))
+  }
+}
+
+impl From<Direction> for Event {
+  fn from(dir: Direction) -> Self {
+    Event::Direction(dir)
+  }
+}
+
+impl From<Input> for Event {
+  fn from(input: Input) -> Self {
+    match input {
+      _ if is_enter(&input) => Event::Enter,
+      Input::Character(c) => Event::Character(c),
+      Input::KeyUp => Event::Direction(Direction::Up),
+      Input::KeyDown => Event::Direction(Direction::Down),
+      Input::KeyLeft => Event::Direction(Direction::Left),
+      Input::KeyRight => Event::Direction(Direction::Right),
+      Input::KeyResize => Event::Resize,
+      other => Event::Other(other),
+    }
+  }
+}
+
+/// Checks if an input is the Enter key. Depending on the platform and the
+/// input mode, Enter can show up as `'\n'`, as `'\r'`, or as
+/// `Input::KeyEnter`, and this accepts all three.
+///
+/// ```rust
+/// use easycurses::{is_enter, Input};
+/// assert!(is_enter(&Input::Character('\n')));
+/// assert!(is_enter(&Input::Character('\r')));
+/// assert!(is_enter(&Input::KeyEnter));
+/// assert!(!is_enter(&Input::Character(' ')));
+/// ```
+pub fn is_enter(input: &Input) -> bool {
+  matches!(*input, Input::Character('\n') | Input::Character('\r') | Input::KeyEnter)
+}
+
+#[cfg(test)]
+mod event_tests {
+  use super::*;
+
+  #[test]
+  fn test_event_from_input() {
+    assert_eq!(Event::from(Input::Character('a')), Event::Character('a'));
+    assert_eq!(Event::from(Input::KeyUp), Event::Direction(Direction::Up));
+    assert_eq!(Event::from(Input::KeyDown), Event::Direction(Direction::Down));
+    assert_eq!(Event::from(Input::KeyLeft), Event::Direction(Direction::Left));
+    assert_eq!(Event::from(Input::KeyRight), Event::Direction(Direction::Right));
+    assert_eq!(Event::from(Input::KeyResize), Event::Resize);
+    assert_eq!(Event::from(Input::KeyF1), Event::Other(Input::KeyF1));
+  }
+
+  #[test]
+  fn test_event_enter() {
+    assert_eq!(Event::from(Input::Character('\n')), Event::Enter);
+    assert_eq!(Event::from(Input::Character('\r')), Event::Enter);
+    assert_eq!(Event::from(Input::KeyEnter), Event::Enter);
+    assert_eq!(Event::from('\n'), Event::Enter);
+  }
+}
+
+/// Which kinds of mouse events to report, for [`EasyCurses::enable_mouse`].
+///
+/// This wraps a curses mouse mask, so any of the `BUTTON` constants from
+/// `pancurses` can be combined with `|` to make one.
+///
+/// ```rust
+/// use easycurses::MouseMask;
+/// let clicks = MouseMask(pancurses::BUTTON1_CLICKED) | MouseMask(pancurses::BUTTON3_CLICKED);
+/// assert!(clicks.contains(MouseMask(pancurses::BUTTON3_CLICKED)));
+/// assert!(!clicks.contains(MouseMask(pancurses::BUTTON2_CLICKED)));
+/// ```
+///
+/// [`EasyCurses::enable_mouse`]: struct.EasyCurses.html#method.enable_mouse
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MouseMask(pub pancurses::mmask_t);
+
+impl MouseMask {
+  /// No mouse events, which turns mouse reporting off.
+  pub const NONE: MouseMask = MouseMask(0);
+  /// Every kind of button event, but not plain movement.
+  pub const ALL: MouseMask = MouseMask(pancurses::ALL_MOUSE_EVENTS);
+
+  /// Checks if every event in `other` is also in this mask.
+  pub fn contains(self, other: MouseMask) -> bool {
+    self.0 & other.0 == other.0
+  }
+}
+
+impl std::ops::BitOr for MouseMask {
+  type Output = MouseMask;
+
+  fn bitor(self, rhs: MouseMask) -> MouseMask {
+    MouseMask(self.0 | rhs.0)
+  }
+}
+
+/// A mouse button. Most terminals report the scroll wheel as buttons 4 and 5.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum MouseButton {
+  Left,
+  Middle,
+  Right,
+  WheelUp,
+  WheelDown,
+}
+
+/// Every event bit that belongs to each `MouseButton`.
+const MOUSE_BUTTON_MASKS: [(MouseButton, pancurses::mmask_t); 5] = [
+  (
+    MouseButton::Left,
+    pancurses::BUTTON1_PRESSED
+      | pancurses::BUTTON1_RELEASED
+      | pancurses::BUTTON1_CLICKED
+      | pancurses::BUTTON1_DOUBLE_CLICKED
+      | pancurses::BUTTON1_TRIPLE_CLICKED,
+  ),
+  (
+    MouseButton::Middle,
+    pancurses::BUTTON2_PRESSED
+      | pancurses::BUTTON2_RELEASED
+      | pancurses::BUTTON2_CLICKED
+      | pancurses::BUTTON2_DOUBLE_CLICKED
+      | pancurses::BUTTON2_TRIPLE_CLICKED,
+  ),
+  (
+    MouseButton::Right,
+    pancurses::BUTTON3_PRESSED
+      | pancurses::BUTTON3_RELEASED
+      | pancurses::BUTTON3_CLICKED
+      | pancurses::BUTTON3_DOUBLE_CLICKED
+      | pancurses::BUTTON3_TRIPLE_CLICKED,
+  ),
+  (
+    MouseButton::WheelUp,
+    pancurses::BUTTON4_PRESSED
+      | pancurses::BUTTON4_RELEASED
+      | pancurses::BUTTON4_CLICKED
+      | pancurses::BUTTON4_DOUBLE_CLICKED
+      | pancurses::BUTTON4_TRIPLE_CLICKED,
+  ),
+  (
+    MouseButton::WheelDown,
+    pancurses::BUTTON5_PRESSED
+      | pancurses::BUTTON5_RELEASED
+      | pancurses::BUTTON5_CLICKED
+      | pancurses::BUTTON5_DOUBLE_CLICKED
+      | pancurses::BUTTON5_TRIPLE_CLICKED,
+  ),
+];
+
+/// One mouse event, as read with [`EasyCurses::get_mouse_event`] or given
+/// as an `Event::Mouse` by `EasyCurses::get_event`.
+///
+/// [`EasyCurses::get_mouse_event`]: struct.EasyCurses.html#method.get_mouse_event
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MouseEvent {
+  /// The row of the screen that the mouse was on.
+  pub row: i32,
+  /// The column of the screen that the mouse was on.
+  pub col: i32,
+  /// The button the event was for, or `None` when the mouse only moved.
+  pub button: Option<MouseButton>,
+  /// Exactly what happened, such as `BUTTON1_CLICKED`, along with any of
+  /// `BUTTON_SHIFT`, `BUTTON_CTRL`, and `BUTTON_ALT` that were held.
+  pub state: MouseMask,
+}
+
+impl MouseEvent {
+  /// Builds an event from the position and button state that curses gives.
+  fn from_curses(x: i32, y: i32, bstate: pancurses::mmask_t) -> Self {
+    let button = MOUSE_BUTTON_MASKS
+      .iter()
+      .find(|&&(_, mask)| bstate & mask != 0)
+      .map(|&(button, _)| button);
+    MouseEvent {
+      row: y,
+      col: x,
+      button,
+      state: MouseMask(bstate),
+    }
+  }
+}
+
+#[cfg(test)]
+mod mouse_tests {
+  use super::*;
+
+  #[test]
+  fn test_mouse_event_buttons() {
+    let click = MouseEvent::from_curses(4, 2, pancurses::BUTTON1_CLICKED);
+    assert_eq!((click.row, click.col), (2, 4));
+    assert_eq!(click.button, Some(MouseButton::Left));
+    let right = MouseEvent::from_curses(0, 0, pancurses::BUTTON3_PRESSED | pancurses::BUTTON_SHIFT);
+    assert_eq!(right.button, Some(MouseButton::Right));
+    assert!(right.state.contains(MouseMask(pancurses::BUTTON_SHIFT)));
+    assert_eq!(MouseEvent::from_curses(0, 0, pancurses::BUTTON5_PRESSED).button, Some(MouseButton::WheelDown));
+  }
+
+  #[test]
+  fn test_mouse_event_motion() {
+    let motion = MouseEvent::from_curses(1, 1, pancurses::REPORT_MOUSE_POSITION);
+    assert_eq!(motion.button, None);
+  }
+}
+
+/// The text attributes that a character cell can have.
+///
+/// Not every terminal can display every attribute.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Attribute {
+  /// Bold (or sometimes "bright") text.
+  Bold,
+  /// Underlined text.
+  Underline,
+  /// Half-bright text.
+  Dim,
+  /// Foreground and background colors swapped.
+  Reverse,
+  /// The terminal's "best highlighting mode".
+  Standout,
+  /// Blinking text.
+  Blink,
+  /// Italic text.
+  Italic,
+  /// Invisible text.
+  Invisible,
+  /// Characters are drawn from the alternate character set (this is how the
+  /// `constants::acs` characters work).
+  AltCharset,
+  /// Protected text, which some terminals won't let be erased or changed.
+  Protect,
+  /// Horizontal highlighting. Very few terminals do anything with this, and
+  /// PDCurses doesn't have it at all.
+  HorizontalHighlight,
+}
+
+impl Attribute {
+  /// Provides a handy Iterator over all of the Attribute values.
+  pub fn attribute_iterator() -> AttributeIter {
+    use Attribute::*;
+    #[allow(non_upper_case_globals)]
+    static attributes: &[Attribute] = &[
+      Bold,
+      Underline,
+      Dim,
+      Reverse,
+      Standout,
+      Blink,
+      Italic,
+      Invisible,
+      AltCharset,
+      Protect,
+      HorizontalHighlight,
+    ];
+    attributes.iter().cloned()
+  }
+}
+
+type AttributeIter = std::iter::Cloned<std::slice::Iter<'static, Attribute>>;
+
+/// Converts an `Attribute` to the curses bits used for it.
+fn attribute_to_chtype(attribute: Attribute) -> pancurses::chtype {
+  use pancurses::Attribute as PA;
+  match attribute {
+    Attribute::Bold => PA::Bold.into(),
+    Attribute::Underline => PA::Underline.into(),
+    Attribute::Dim => PA::Dim.into(),
+    Attribute::Reverse => PA::Reverse.into(),
+    Attribute::Standout => standout_chtype(),
+    Attribute::Blink => PA::Blink.into(),
+    Attribute::Italic => PA::Italic.into(),
+    Attribute::Invisible => PA::Invisible.into(),
+    Attribute::AltCharset => PA::AlternativeCharSet.into(),
+    Attribute::Protect => PROTECT_CHTYPE,
+    Attribute::HorizontalHighlight => HORIZONTAL_CHTYPE,
+  }
+}
+
+/// A saved copy of the whole text style: every attribute that's on, and the
+/// color pair. Get one with [`EasyCurses::get_attributes`] and put it back
+/// with [`EasyCurses::set_attributes`].
+///
+/// [`EasyCurses::get_attributes`]: struct.EasyCurses.html#method.get_attributes
+/// [`EasyCurses::set_attributes`]: struct.EasyCurses.html#method.set_attributes
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Attributes {
+  bits: pancurses::chtype,
+  pair: ColorPair,
+  /// The curses pair id that was actually selected, which covers 256 color
+  /// and gradient pairs too.
+  pair_id: i16,
+}
+
+impl Attributes {
+  /// Checks if an attribute is on.
+  pub fn contains(&self, attribute: Attribute) -> bool {
+    let bits = attribute_to_chtype(attribute);
+    bits != 0 && self.bits & bits == bits
+  }
+
+  /// The color pair. While a pair from `set_color_pair_256` is in use this
+  /// is the last basic `ColorPair` that was set, but `set_attributes` still
+  /// puts back the 256 color pair.
+  pub fn color_pair(&self) -> ColorPair {
+    self.pair
+  }
+}
+
+/// pancurses doesn't export `A_PROTECT` or `A_HORIZONTAL`, so these are the
+/// values from ncurses' `curses.h`.
+#[cfg(unix)]
+const PROTECT_CHTYPE: pancurses::chtype = 1 << 24;
+#[cfg(unix)]
+const HORIZONTAL_CHTYPE: pancurses::chtype = 1 << 25;
+
+/// PDCurses defines protect as underline with the left and right lines, and
+/// has no horizontal highlight.
+#[cfg(not(unix))]
+const PROTECT_CHTYPE: pancurses::chtype = pancurses::A_UNDERLINE | pancurses::A_LEFTLINE | pancurses::A_RIGHTLINE;
+#[cfg(not(unix))]
+const HORIZONTAL_CHTYPE: pancurses::chtype = 0;
+
+#[cfg(unix)]
+fn standout_chtype() -> pancurses::chtype {
+  pancurses::A_STANDOUT
+}
+
+/// PDCurses defines standout as reverse and bold together.
+#[cfg(not(unix))]
+fn standout_chtype() -> pancurses::chtype {
+  pancurses::A_REVERSE | pancurses::A_BOLD
+}
+
+/// The bits of a `chtype` that hold the character itself.
+///
+/// pancurses' own `A_CHARTEXT` is wrong for ncurses, so we use our own value
+/// there.
+#[cfg(unix)]
+const CHARTEXT_MASK: pancurses::chtype = 0xFF;
+
+/// The bits of a `chtype` that hold the character itself.
+#[cfg(not(unix))]
+const CHARTEXT_MASK: pancurses::chtype = pancurses::A_CHARTEXT;
+
+/// Splits a `chtype` read from the screen into its character, color pair,
+/// and attributes.
+// `chtype` isn't a `u32` on every platform, so the cast is needed elsewhere.
+#[allow(clippy::unnecessary_cast)]
+fn decode_chtype(ch: pancurses::chtype) -> (char, ColorPair, Vec<Attribute>) {
+  let character = std::char::from_u32((ch & CHARTEXT_MASK) as u32).unwrap_or(' ');
+  let pair_id = ((ch & pancurses::A_COLOR) >> pancurses::A_COLOR.trailing_zeros()) as i16;
+  let pair = if pair_id == 0 { ColorPair::default() } else { ColorPair(pair_id) };
+  let attributes = Attribute::attribute_iterator()
+    .filter(|&attribute| {
+      let bits = attribute_to_chtype(attribute);
+      bits != 0 && ch & bits == bits
+    })
+    .collect();
+  (character, pair, attributes)
+}
+
+#[cfg(test)]
+mod attribute_tests {
+  use super::*;
+
+  #[test]
+  fn test_decode_chtype() {
+    let pair = ColorPair::new(Color::Green, Color::Black);
+    let ch = 'x' as pancurses::chtype | pancurses::COLOR_PAIR(pair.0 as pancurses::chtype) | attribute_to_chtype(Attribute::Underline);
+    let (character, decoded_pair, attributes) = decode_chtype(ch);
+    assert_eq!(character, 'x');
+    assert_eq!(decoded_pair, pair);
+    assert_eq!(attributes, vec![Attribute::Underline]);
+  }
+
+  #[test]
+  fn test_decode_chtype_pair_zero_is_default() {
+    let (character, pair, attributes) = decode_chtype('a' as pancurses::chtype);
+    assert_eq!(character, 'a');
+    assert_eq!(pair, ColorPair::default());
+    assert!(attributes.is_empty());
+  }
+}
+
+/// The various input modes that you can set for the terminal.
+///
+/// Use this with `set_input_mode`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum InputMode {
+  /// Line buffering (special character processing)
+  Cooked,
+  /// Characters visible immediately (special character processing)
+  Character,
+  /// Line buffering (no special processing)
+  ///
+  /// Curses can't actually provide this mode: raw mode always turns off line
+  /// buffering. `set_input_mode` leaves the terminal alone and returns `false`
+  /// if you ask for it.
+  RawCooked,
+  /// Characters visible immediately (no special processing)
+  RawCharacter,
+}
+
+/// The character encodings that `print` can send its output in.
+///
+/// Use this with `set_output_encoding`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum OutputEncoding {
+  /// Text is passed to curses as-is.
+  Utf8,
+  /// The original IBM PC code page, the usual default of a Windows console.
+  Cp437,
+  /// ISO 8859-1, where each character from U+0000 to U+00FF is one byte.
+  Latin1,
+}
+
+impl Default for OutputEncoding {
+  /// ```rust
+  /// use easycurses::OutputEncoding;
+  /// assert_eq!(OutputEncoding::default(), OutputEncoding::Utf8);
+  /// ```
+  fn default() -> Self {
+    OutputEncoding::Utf8
+  }
+}
+
+/// What `print` does with ASCII control characters, such as `'\x07'` (bell)
+/// or `'\x1b'` (escape), which otherwise have unpredictable results.
+/// Newlines and tabs are always left alone.
+///
+/// Use this with `set_control_char_policy`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum ControlCharPolicy {
+  /// Control characters are passed to curses as-is.
+  Raw,
+  /// Control characters are shown in caret notation, such as `^G` or `^[`.
+  Caret,
+  /// Control characters are dropped.
+  Strip,
+}
+
+impl Default for ControlCharPolicy {
+  /// ```rust
+  /// use easycurses::ControlCharPolicy;
+  /// assert_eq!(ControlCharPolicy::default(), ControlCharPolicy::Raw);
+  /// ```
+  fn default() -> Self {
+    ControlCharPolicy::Raw
+  }
+}
+
+/// The kinds of bell that `bell` (and `request_bell`) can give.
+///
+/// Use this with `set_bell_mode`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum BellMode {
+  /// An audible beep, see `beep`.
+  Beep,
+  /// A flash of the whole screen, see `flash`.
+  Flash,
+  /// A brief highlight of just the edge of the window, see `gentle_bell`.
+  /// This is much easier on photosensitive users than a full screen flash.
+  Gentle,
+}
+
+impl Default for BellMode {
+  /// ```rust
+  /// use easycurses::BellMode;
+  /// assert_eq!(BellMode::default(), BellMode::Beep);
+  /// ```
+  fn default() -> Self {
+    BellMode::Beep
+  }
+}
+
+/// How long `gentle_bell` keeps the edge of the window highlighted, in
+/// milliseconds.
+const GENTLE_BELL_MS: i32 = 150;
+
+/// Applies a control character policy to some text. Text without any control
+/// characters (other than newlines and tabs) is passed back unchanged.
+fn apply_control_char_policy(text: &str, policy: ControlCharPolicy) -> Cow<'_, str> {
+  let is_affected = |c: char| c.is_ascii_control() && c != '\n' && c != '\t';
+  if policy == ControlCharPolicy::Raw || !text.chars().any(is_affected) {
+    return Cow::Borrowed(text);
+  }
+  let mut out = String::with_capacity(text.len() + 8);
+  for c in text.chars() {
+    if !is_affected(c) {
+      out.push(c);
+    } else if policy == ControlCharPolicy::Caret {
+      out.push('^');
+      out.push((c as u8 ^ 0x40) as char);
+    }
+  }
+  Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod control_char_tests {
+  use super::*;
+
+  #[test]
+  fn test_control_char_policies() {
+    let text = "a\x07b\x1bc\n\td";
+    assert_eq!(apply_control_char_policy(text, ControlCharPolicy::Raw), text);
+    assert_eq!(apply_control_char_policy(text, ControlCharPolicy::Caret), "a^Gb^[c\n\td");
+    assert_eq!(apply_control_char_policy(text, ControlCharPolicy::Strip), "abc\n\td");
+    assert_eq!(apply_control_char_policy("\x00\x7f", ControlCharPolicy::Caret), "^@^?");
+  }
+
+  #[test]
+  fn test_control_char_policy_borrows_clean_text() {
+    match apply_control_char_policy("plain\ttext\n", ControlCharPolicy::Caret) {
+      Cow::Borrowed(_) => (),
+      Cow::Owned(_) => panic!("clean text was copied"),
+    }
+  }
+}
+
+/// The characters for bytes 0x80 through 0xFF of code page 437.
+const CP437_HIGH: [char; 128] = [
+  'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+  'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+  'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+  '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+  '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+  '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+  'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+  '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Converts a character to its single byte in a legacy encoding, giving `?`
+/// for characters that the encoding doesn't have. ASCII is the same in every
+/// encoding.
+fn encode_legacy_char(encoding: OutputEncoding, c: char) -> u8 {
+  if c.is_ascii() {
+    return c as u8;
+  }
+  match encoding {
+    OutputEncoding::Utf8 => b'?',
+    OutputEncoding::Latin1 => {
+      if (c as u32) <= 0xFF {
+        c as u8
+      } else {
+        b'?'
+      }
+    }
+    OutputEncoding::Cp437 => CP437_HIGH.iter().position(|&high| high == c).map_or(b'?', |index| 0x80 + index as u8),
+  }
+}
+
+#[cfg(test)]
+mod encoding_tests {
+  use super::*;
+
+  #[test]
+  fn test_encode_cp437() {
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, 'a'), b'a');
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, 'é'), 0x82);
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, '½'), 0xAB);
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, '░'), 0xB0);
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, '\u{a0}'), 0xFF);
+    assert_eq!(encode_legacy_char(OutputEncoding::Cp437, '€'), b'?');
+  }
+
+  #[test]
+  fn test_encode_latin1() {
+    assert_eq!(encode_legacy_char(OutputEncoding::Latin1, '\n'), b'\n');
+    assert_eq!(encode_legacy_char(OutputEncoding::Latin1, 'é'), 0xE9);
+    assert_eq!(encode_legacy_char(OutputEncoding::Latin1, '░'), b'?');
+  }
+}
+
+/// The various timeouts that you can set for `get_input` to operate with.
+///
+/// Use this with the `set_input_timeout` method.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum TimeoutMode {
+  /// If no input is available, return `None`.
+  Immediate,
+  /// Wait up to this many milliseconds before returning `None`.
+  WaitUpTo(i32),
+  /// Block until input is given.
+  Never,
+}
+
+impl Default for TimeoutMode {
+  /// ```rust
+  /// use easycurses::TimeoutMode;
+  /// assert_eq!(TimeoutMode::default(), TimeoutMode::Never);
+  /// ```
+  fn default() -> Self {
+    TimeoutMode::Never
+  }
+}
+
+/// Converts a `pancurses::OK` value into `true`, and all other values into
+/// `false`.
+fn to_bool(curses_bool: i32) -> bool {
+  curses_bool == pancurses::OK
+}
+
+/// Asks the operating system for the size of the terminal as `(rows, cols)`.
+///
+/// On unix this runs `stty size` against the controlling terminal. On other
+/// systems there's no way to ask without unsafe code, so you always get `None`.
+#[cfg(unix)]
+fn os_terminal_size() -> Option<(i32, i32)> {
+  use std::fs::File;
+  use std::process::Command;
+  let tty = File::open("/dev/tty").ok()?;
+  let output = Command::new("stty").arg("size").stdin(tty).output().ok()?;
+  if output.status.success() {
+    parse_stty_size(&String::from_utf8_lossy(&output.stdout))
+  } else {
+    None
+  }
+}
+
+/// Asks the operating system for the size of the terminal as `(rows, cols)`.
+///
+/// On unix this runs `stty size` against the controlling terminal. On other
+/// systems there's no way to ask without unsafe code, so you always get `None`.
+#[cfg(not(unix))]
+fn os_terminal_size() -> Option<(i32, i32)> {
+  None
+}
+
+/// How long `dimensions_match_terminal` reuses the terminal size from the
+/// operating system before asking again.
+const OS_SIZE_CACHE_MS: u64 = 500;
+
+/// Gives the cached terminal size if it's no older than `OS_SIZE_CACHE_MS`,
+/// otherwise calls `fetch` for a fresh one and caches that.
+fn cached_os_size<F: FnOnce() -> Option<(i32, i32)>>(
+  cache: &mut Option<(Instant, Option<(i32, i32)>)>,
+  now: Instant,
+  fetch: F,
+) -> Option<(i32, i32)> {
+  if let Some((fetched_at, size)) = *cache {
+    if now >= fetched_at && now - fetched_at <= Duration::from_millis(OS_SIZE_CACHE_MS) {
+      return size;
+    }
+  }
+  let size = fetch();
+  *cache = Some((now, size));
+  size
+}
+
+/// Parses the `"rows cols"` output of `stty size`.
+#[cfg_attr(not(any(unix, test)), allow(dead_code))]
+fn parse_stty_size(output: &str) -> Option<(i32, i32)> {
+  let mut parts = output.split_whitespace();
+  let rows = parts.next()?.parse().ok()?;
+  let cols = parts.next()?.parse().ok()?;
+  if parts.next().is_none() {
+    Some((rows, cols))
+  } else {
+    None
+  }
+}
+
+/// Checks a `(rows, cols)` size against a minimum, giving back the size as
+/// the error when either count is too small.
+fn check_min_size(size: (i32, i32), min_rows: i32, min_cols: i32) -> Result<(), (i32, i32)> {
+  if size.0 >= min_rows && size.1 >= min_cols {
+    Ok(())
+  } else {
+    Err(size)
+  }
+}
+
+#[cfg(test)]
+mod terminal_size_tests {
+  use super::*;
+
+  #[test]
+  fn test_check_min_size() {
+    assert_eq!(check_min_size((24, 80), 24, 80), Ok(()));
+    assert_eq!(check_min_size((50, 132), 24, 80), Ok(()));
+    assert_eq!(check_min_size((20, 80), 24, 80), Err((20, 80)));
+    assert_eq!(check_min_size((24, 40), 24, 80), Err((24, 40)));
+  }
+
+  #[test]
+  fn test_cached_os_size() {
+    let start = Instant::now();
+    let mut cache = None;
+    let mut fetches = 0;
+    let mut fetch = |size| {
+      fetches += 1;
+      size
+    };
+    assert_eq!(cached_os_size(&mut cache, start, || fetch(Some((24, 80)))), Some((24, 80)));
+    let later = start + Duration::from_millis(OS_SIZE_CACHE_MS);
+    assert_eq!(cached_os_size(&mut cache, later, || fetch(Some((50, 132)))), Some((24, 80)));
+    let expired = later + Duration::from_millis(1);
+    assert_eq!(cached_os_size(&mut cache, expired, || fetch(Some((50, 132)))), Some((50, 132)));
+    cache = None;
+    assert_eq!(cached_os_size(&mut cache, expired, || fetch(None)), None);
+    assert_eq!(fetches, 3);
+  }
+
+  #[test]
+  fn test_parse_stty_size() {
+    assert_eq!(parse_stty_size("24 80\n"), Some((24, 80)));
+    assert_eq!(parse_stty_size("  50   132 "), Some((50, 132)));
+    assert_eq!(parse_stty_size(""), None);
+    assert_eq!(parse_stty_size("24"), None);
+    assert_eq!(parse_stty_size("24 80 1"), None);
+    assert_eq!(parse_stty_size("rows cols"), None);
+  }
+}
+
+/// Runs `refresh` and, if it fails, runs it once more as a retry. The
+/// argument tells the closure if it's the retry, so it can recover (such as
+/// by resizing) first.
+fn refresh_with_retry<F: FnMut(bool) -> bool>(mut refresh: F) -> bool {
+  refresh(false) || refresh(true)
+}
+
+#[cfg(test)]
+mod refresh_tests {
+  use super::*;
+
+  #[test]
+  fn test_refresh_with_retry_success() {
+    let mut attempts = Vec::new();
+    assert!(refresh_with_retry(|retry| {
+      attempts.push(retry);
+      true
+    }));
+    assert_eq!(attempts, vec![false]);
+  }
+
+  #[test]
+  fn test_refresh_with_retry_recovers() {
+    let mut attempts = Vec::new();
+    assert!(refresh_with_retry(|retry| {
+      attempts.push(retry);
+      retry
+    }));
+    assert_eq!(attempts, vec![false, true]);
+  }
+
+  #[test]
+  fn test_refresh_with_retry_gives_up() {
+    let mut attempts = Vec::new();
+    assert!(!refresh_with_retry(|retry| {
+      attempts.push(retry);
+      false
+    }));
+    assert_eq!(attempts, vec![false, true]);
+  }
+}
+
+/// Asks the terminfo database if it knows about the given terminal type.
+///
+/// On unix this runs `infocmp`. If that can't be run at all, or on other
+/// systems, there's no answer and you get `None`.
+#[cfg(unix)]
+fn terminfo_known(term: &str) -> Option<bool> {
+  use std::process::{Command, Stdio};
+  let status = Command::new("infocmp").arg(term).stdout(Stdio::null()).stderr(Stdio::null()).status().ok()?;
+  Some(status.success())
+}
+
+/// Asks the terminfo database if it knows about the given terminal type.
+///
+/// On unix this runs `infocmp`. If that can't be run at all, or on other
+/// systems, there's no answer and you get `None`.
+#[cfg(not(unix))]
+fn terminfo_known(_term: &str) -> Option<bool> {
+  None
+}
+
+/// Why [`EasyCurses::try_initialize`] refused to start curses.
+///
+/// [`EasyCurses::try_initialize`]: struct.EasyCurses.html#method.try_initialize
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InitError {
+  /// Standard output isn't a terminal, such as when it's piped to a file.
+  NotATerminal,
+  /// The `TERM` environment variable is missing or empty.
+  NoTerminalType,
+  /// The terminfo database doesn't know the terminal type named by `TERM`.
+  UnknownTerminal(String),
+  /// Curses is already on, see `initialize_system`.
+  AlreadyInitialized,
+}
+
+impl fmt::Display for InitError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      InitError::NotATerminal => write!(f, "standard output is not a terminal"),
+      InitError::NoTerminalType => write!(f, "the TERM environment variable is not set"),
+      InitError::UnknownTerminal(ref term) => write!(f, "unknown terminal type {:?}", term),
+      InitError::AlreadyInitialized => write!(f, "curses is already initialized"),
+    }
+  }
+}
+
+impl std::error::Error for InitError {}
+
+/// The checks that `try_initialize` makes on unix before starting curses.
+/// `term_known` is `None` when the terminfo database couldn't be asked.
+fn check_terminal(stdout_is_tty: bool, term: Option<&str>, term_known: Option<bool>) -> Result<(), InitError> {
+  if !stdout_is_tty {
+    return Err(InitError::NotATerminal);
+  }
+  match term {
+    None | Some("") => Err(InitError::NoTerminalType),
+    Some(term) if term_known == Some(false) => Err(InitError::UnknownTerminal(term.to_string())),
+    Some(_) => Ok(()),
+  }
+}
+
+#[cfg(test)]
+mod init_check_tests {
+  use super::*;
+
+  #[test]
+  fn test_check_terminal_refuses_non_tty() {
+    assert_eq!(check_terminal(false, Some("xterm"), Some(true)), Err(InitError::NotATerminal));
+  }
+
+  #[test]
+  fn test_check_terminal_term() {
+    assert_eq!(check_terminal(true, None, None), Err(InitError::NoTerminalType));
+    assert_eq!(check_terminal(true, Some(""), None), Err(InitError::NoTerminalType));
+    assert_eq!(check_terminal(true, Some("bogus"), Some(false)), Err(InitError::UnknownTerminal("bogus".to_string())));
+    assert_eq!(check_terminal(true, Some("xterm"), Some(true)), Ok(()));
+    assert_eq!(check_terminal(true, Some("xterm"), None), Ok(()));
+  }
+}
+
+/// This is a handle to all your fun curses functionality.
+///
+/// `EasyCurses` will automatically restore the terminal when you drop it, so
+/// you don't need to worry about any manual cleanup. Automatic cleanup will
+/// happen even if your program panics and unwinds, but it **will not** happen
+/// if your program panics and aborts (obviously). So, don't abort the program
+/// while curses is active, or your terminal session will just be ruined.
+///
+/// Except in the case of [`is_color_terminal`], all `EasyCurses` methods that
+/// return a `bool` use it to indicate if the requested operation was successful
+/// or not. Unfortunately, the curses library doesn't provide any more info than
+/// that, so a `bool` is all you get.
+///
+/// [`is_color_terminal`]: #method.is_color_terminal
+#[derive(Debug)]
+pub struct EasyCurses {
+  /// This is the inner pancurses `Window` that the `EasyCurses` type wraps
+  /// over.
+  ///
+  /// This is only intended to be used as a last resort, if you really want to
+  /// call something that's not here. Under normal circumstances you shouldn't
+  /// need to touch this field at all. It's not "unsafe" to use in the
+  /// rust/memory sense, but if you access this field and then cause a bug in
+  /// `EasyCurses`, well that's your own fault.
+  ///
+  /// Prefer the `window` and `window_mut` accessors, since this field will
+  /// likely become private in a future breaking release.
+  pub win: pancurses::Window,
+  color_support: bool,
+  /// Bitmask of the `ColorPair` ids that were successfully set up during
+  /// initialization, see `pair_bit`.
+  initialized_pairs: u64,
+  /// When pairs are set up the first time they're used instead of all at
+  /// initialization (see `initialize_system_lazy`), this tracks the pair id
+  /// given to each combination so far.
+  pair_cache: Option<PairCache>,
+  /// The pair ids given to 256 color pairs so far.
+  pairs_256: Pair256Cache,
+  color_remap: [Color; 8],
+  /// The pair last picked with `set_color_pair`, or `None` while curses is
+  /// still on pair 0 from startup, which looks like `ColorPair::default()`.
+  color_pair: Option<ColorPair>,
+  echo: bool,
+  keypad: bool,
+  bold: bool,
+  underline: bool,
+  quiet: bool,
+  bell_count: u32,
+  bell_requested: bool,
+  bell_mode: BellMode,
+  left_margin: i32,
+  scroll_region_bottom: Option<i32>,
+  follow_tail: bool,
+  viewport: Viewport,
+  tab_expansion: Option<u8>,
+  output_encoding: OutputEncoding,
+  control_char_policy: ControlCharPolicy,
+  cursor_visibility: CursorVisibility,
+  input_timeout: TimeoutMode,
+  /// When each key was last passed to `is_key_repeating`.
+  key_last_seen: HashMap<Input, Instant>,
+  /// If queued `KeyResize` events are merged, see `set_resize_coalescing`.
+  resize_coalescing: bool,
+  /// The terminal size last reported by the operating system and when it
+  /// was asked for, see `dimensions_match_terminal`.
+  os_size_cache: Option<(Instant, Option<(i32, i32)>)>,
+  /// Determines if the window will automatically resize itself when
+  /// `KeyResize` comes in through the input channel. Defaults to true. If you
+  /// disable this and then don't call resize yourself then `KeyResize` comes
+  /// in you'll have a bad time.
+  pub auto_resize: bool,
+}
+
+impl Drop for EasyCurses {
+  /// Dropping EasyCurses causes the
+  /// [endwin](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/endwin.html)
+  /// curses function to be called.
+  fn drop(&mut self) {
+    // We will assume that the initialization code is correctly never
+    // initializing curses twice, and thus we will assume that it's safe to
+    // call endwin and then store that curses is off once that's done. If we
+    // were paranoid we'd do another compare_and_swap, but that's slower for
+    // no reason (again, assuming that the initialization code is correct).
+    end_curses_with(|| {
+      pancurses::endwin();
+    });
+  }
+}
+
+/// Stores that curses is off when dropped, so that it happens even if ending
+/// curses unwinds.
+struct CursesOffGuard;
+
+impl Drop for CursesOffGuard {
+  fn drop(&mut self) {
+    curses_is_on.store(false, Ordering::SeqCst);
+  }
+}
+
+/// Runs `endwin` and then stores that curses is off. The flag is cleared even
+/// if `endwin` panics, so that a later `initialize_system` isn't locked out.
+fn end_curses_with<F: FnOnce()>(endwin: F) {
+  let _off = CursesOffGuard;
+  endwin();
+}
+
+#[cfg(test)]
+mod curses_off_tests {
+  use super::*;
+
+  #[test]
+  fn test_flag_cleared_when_endwin_panics() {
+    curses_is_on.store(true, Ordering::SeqCst);
+    let result = catch_unwind(|| end_curses_with(|| panic!("endwin failed")));
+    assert!(result.is_err());
+    assert!(!curses_is_on.load(Ordering::SeqCst));
+  }
+}
+
+/// Collects the usual startup settings and applies them all right after
+/// curses is initialized, see [`EasyCurses::builder`].
+///
+/// Any setting that isn't given is left at the curses default.
+///
+/// [`EasyCurses::builder`]: struct.EasyCurses.html#method.builder
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct EasyCursesBuilder {
+  cursor_visibility: Option<CursorVisibility>,
+  echo: Option<bool>,
+  keypad: Option<bool>,
+  input_mode: Option<InputMode>,
+  input_timeout: Option<TimeoutMode>,
+  scrolling: Option<bool>,
+}
+
+impl EasyCursesBuilder {
+  /// Sets the cursor visibility, see `EasyCurses::set_cursor_visibility`.
+  /// If the terminal can't show that mode, the cursor is left as it was.
+  pub fn cursor_visibility(self, vis: CursorVisibility) -> Self {
+    EasyCursesBuilder {
+      cursor_visibility: Some(vis),
+      ..self
+    }
+  }
+
+  /// Turns echoing on or off, see `EasyCurses::set_echo`.
+  pub fn echo(self, echoing: bool) -> Self {
+    EasyCursesBuilder {
+      echo: Some(echoing),
+      ..self
+    }
+  }
+
+  /// Turns special key processing on or off, see
+  /// `EasyCurses::set_keypad_enabled`.
+  pub fn keypad(self, use_keypad: bool) -> Self {
+    EasyCursesBuilder {
+      keypad: Some(use_keypad),
+      ..self
+    }
+  }
+
+  /// Sets the input mode, see `EasyCurses::set_input_mode`.
+  pub fn input_mode(self, mode: InputMode) -> Self {
+    EasyCursesBuilder {
+      input_mode: Some(mode),
+      ..self
+    }
+  }
+
+  /// Sets the input timeout, see `EasyCurses::set_input_timeout`.
+  pub fn input_timeout(self, mode: TimeoutMode) -> Self {
+    EasyCursesBuilder {
+      input_timeout: Some(mode),
+      ..self
+    }
+  }
+
+  /// Turns scrolling on or off, see `EasyCurses::set_scrolling`.
+  pub fn scrolling(self, scrolling: bool) -> Self {
+    EasyCursesBuilder {
+      scrolling: Some(scrolling),
+      ..self
+    }
+  }
+
+  /// Initializes curses with `EasyCurses::initialize_system` and then applies
+  /// each of the settings that were given. Gives `None` under the same
+  /// conditions as `initialize_system`. A setting that the terminal can't
+  /// apply (such as a cursor visibility it doesn't support) is skipped, just
+  /// as if its setter had been called and the result ignored.
+  pub fn initialize(self) -> Option<EasyCurses> {
+    let mut easy = EasyCurses::initialize_system()?;
+    if let Some(mode) = self.input_mode {
+      easy.set_input_mode(mode);
+    }
+    if let Some(echoing) = self.echo {
+      easy.set_echo(echoing);
+    }
+    if let Some(use_keypad) = self.keypad {
+      easy.set_keypad_enabled(use_keypad);
+    }
+    if let Some(mode) = self.input_timeout {
+      easy.set_input_timeout(mode);
+    }
+    if let Some(vis) = self.cursor_visibility {
+      easy.set_cursor_visibility(vis);
+    }
+    if let Some(scrolling) = self.scrolling {
+      easy.set_scrolling(scrolling);
+    }
+    Some(easy)
+  }
+}
+
+impl EasyCurses {
+  /// Initializes the curses system like `initialize_system`, but first checks
+  /// for the usual reasons that the C layer would print an error and exit
+  /// the process, giving you an `Err` with the reason instead.
+  ///
+  /// The checks are that standard output is a terminal and, on unix, that
+  /// `TERM` is set to a terminal type that the terminfo database knows about
+  /// (asked with `infocmp` if it's installed). This can't catch everything,
+  /// so it's still possible for the C layer to exit the process, but it
+  /// covers the common cases such as output being piped to a file.
+  ///
+  /// ```rust,no_run
+  /// use easycurses::EasyCurses;
+  /// match EasyCurses::try_initialize() {
+  ///     Ok(easy) => drop(easy),
+  ///     Err(reason) => eprintln!("can't start curses: {}", reason),
+  /// }
+  /// ```
+  pub fn try_initialize() -> Result<Self, InitError> {
+    let stdout_is_tty = std::io::stdout().is_terminal();
+    if cfg!(unix) {
+      let term = std::env::var("TERM").ok();
+      let term_known = term.as_ref().and_then(|term| terminfo_known(term));
+      check_terminal(stdout_is_tty, term.as_deref(), term_known)?;
+    } else if !stdout_is_tty {
+      return Err(InitError::NotATerminal);
+    }
+    Self::initialize_system().ok_or(InitError::AlreadyInitialized)
+  }
+
+  /// Initializes the curses system so that you can begin using curses.
+  ///
+  /// The name is long to remind you of the seriousness of attempting to turn
+  /// on curses: If the C layer encounters an error while trying to initialize
+  /// the user's terminal into curses mode it will "helpfully" print an error
+  /// message and exit the process on its own. There's no way to prevent this
+  /// from happening at the Rust level. See `try_initialize` for a version that
+  /// checks for the common causes of that first.
+  ///
+  /// If the terminal supports colors, they are automatically activated and
+  /// `ColorPair` values are initialized for all color foreground and
+  /// background combinations.
+  ///
+  /// # Errors
+  ///
+  /// Curses must not be double-initialized. This is tracked by easycurses
+  /// with an `AtomicBool` being flipped on and off. If it is on when you call
+  /// this method you get `None` back instead.
+  pub fn initialize_system() -> Option<Self> {
+    EasyCurses::initialize_system_with(false)
+  }
+
+  /// Initializes the curses system just like `initialize_system`, except that
+  /// color pairs aren't all set up right away. Instead, each `ColorPair` is
+  /// set up with curses the first time that it's used, by `set_color_pair` or
+  /// `set_color_pair_checked`, and then remembered from then on.
+  ///
+  /// This makes startup quicker for programs that only use a few colors,
+  /// which matters most on slow terminals. The tradeoff is a small one-time
+  /// cost the first time that each pair is used. Since curses pair ids are
+  /// handed out in the order that pairs are first used, this also works on
+  /// terminals with fewer than 64 color pairs, as long as the program doesn't
+  /// use more pairs than the terminal has.
+  ///
+  /// # Errors
+  ///
+  /// The same as with `initialize_system`.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system_lazy().unwrap();
+  /// let pair = ColorPair::new(Color::Cyan, Color::Blue);
+  /// if easy.is_color_terminal() {
+  ///     assert!(easy.set_color_pair_checked(pair));
+  ///     easy.print("x");
+  ///     assert_eq!(easy.dump_styled()[0][0].1, pair);
+  /// }
+  /// ```
+  pub fn initialize_system_lazy() -> Option<Self> {
+    EasyCurses::initialize_system_with(true)
+  }
+
+  /// Starts building an `EasyCurses` with its startup settings given up
+  /// front, instead of a separate call for each one after
+  /// `initialize_system`.
+  ///
+  /// ```rust
+  /// use easycurses::{CursorVisibility, EasyCurses, InputMode, TimeoutMode};
+  /// let easy = EasyCurses::builder()
+  ///     .cursor_visibility(CursorVisibility::Invisible)
+  ///     .echo(false)
+  ///     .keypad(true)
+  ///     .input_mode(InputMode::Character)
+  ///     .input_timeout(TimeoutMode::Immediate)
+  ///     .initialize()
+  ///     .unwrap();
+  /// assert!(easy.is_keypad_enabled());
+  /// // Some terminals can't hide the cursor, and then it's left as it was.
+  /// let vis = easy.get_cursor_visibility();
+  /// assert!(vis == CursorVisibility::Invisible || vis == CursorVisibility::Visible);
+  /// ```
+  pub fn builder() -> EasyCursesBuilder {
+    EasyCursesBuilder::default()
+  }
+
+  /// The actual initialization, optionally leaving the color pairs to be set
+  /// up as they're used.
+  fn initialize_system_with(lazy_pairs: bool) -> Option<Self> {
+    // https://doc.rust-lang.org/std/sync/atomic/struct.AtomicBool.html#method.compare_exchange
+    // We try to turn `curses_is_on` to true and then we're told if we
+    // actually changed it or not. If we did that means it was off and it's
+    // safe to turn it on. If we didn't change it that means it was already on
+    // and we should back out.
+    if curses_is_on.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+      let w = pancurses::initscr();
+      let color_support = if pancurses::has_colors() {
+        to_bool(pancurses::start_color())
+      } else {
+        false
+      };
+      let mut initialized_pairs = 0;
+      if color_support && !lazy_pairs {
+        let color_count = pancurses::COLORS();
+        let pair_count = pancurses::COLOR_PAIRS();
+        if color_count >= 8 && pair_count >= 8 * 8 {
+          for fg in Color::color_iterator() {
+            for bg in Color::color_iterator() {
+              let fgi: i16 = color_to_i16(fg);
+              let bgi: i16 = color_to_i16(bg);
+              let pair_id: i16 = ColorPair::fgbg_pairid(fgi, bgi);
+              debug_assert!(
+                fgi as i32 <= color_count,
+                "Curses reported {} color ids available, but {:?} has id {}",
+                color_count,
+                fg,
+                fgi
+              );
+              debug_assert!(
+                bgi as i32 <= color_count,
+                "Curses reported {} color ids available, but {:?} has id {}",
+                color_count,
+                bg,
+                bgi
+              );
+              debug_assert!(
+                pair_id as i32 <= pair_count,
+                "Curses reported {} colorpair ids available, but {:?} on {:?} would be id {}",
+                pair_count,
+                fg,
+                bg,
+                pair_id
+              );
+              if to_bool(pancurses::init_pair(pair_id, fgi, bgi)) {
+                initialized_pairs |= pair_bit(pair_id);
+              }
+            }
+          }
+        }
+      }
+      Some(EasyCurses {
+        win: w,
+        color_support,
+        initialized_pairs,
+        pair_cache: if lazy_pairs { Some(PairCache::default()) } else { None },
+        pairs_256: Pair256Cache::default(),
+        color_remap: IDENTITY_REMAP,
+        color_pair: None,
+        echo: true,
+        keypad: false,
+        bold: false,
+        underline: false,
+        quiet: false,
+        bell_count: 0,
+        bell_requested: false,
+        bell_mode: BellMode::Beep,
+        left_margin: 0,
+        scroll_region_bottom: None,
+        follow_tail: true,
+        viewport: Viewport::default(),
+        tab_expansion: None,
+        output_encoding: OutputEncoding::Utf8,
+        control_char_policy: ControlCharPolicy::Raw,
+        cursor_visibility: CursorVisibility::Visible,
+        input_timeout: TimeoutMode::Never,
+        key_last_seen: HashMap::new(),
+        resize_coalescing: true,
+        os_size_cache: None,
+        auto_resize: true,
+      })
+    } else {
+      None
+    }
+  }
+
+  /// Runs some drawing and then puts the cursor position and the text style
+  /// (color pair, bold, underline, and any other attributes) back the way
+  /// they were. This is for drawing things like a status line without
+  /// disturbing the cursor and style of whatever the user is editing.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(3, 7);
+  /// easy.set_underline(true);
+  /// let attrs_before = easy.win.attrget();
+  /// easy.draw_overlay(|easy| {
+  ///     let (row_count, _) = easy.get_row_col_count();
+  ///     easy.move_rc(row_count - 1, 0);
+  ///     easy.set_underline(false);
+  ///     easy.set_bold(true);
+  ///     easy.set_color_pair(ColorPair::new(Color::Black, Color::White));
+  ///     easy.print("status");
+  /// });
+  /// assert_eq!(easy.get_cursor_rc(), (3, 7));
+  /// assert!(easy.is_underline());
+  /// assert!(!easy.is_bold());
+  /// assert_eq!(easy.win.attrget(), attrs_before);
+  /// ```
+  pub fn draw_overlay<F: FnOnce(&mut EasyCurses)>(&mut self, f: F) {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (old_attrs, old_pair_id) = self.win.attrget();
+    let (old_pair, old_bold, old_underline) = (self.color_pair, self.bold, self.underline);
+    f(self);
+    self.win.attrset(old_attrs);
+    self.win.color_set(old_pair_id);
+    self.color_pair = old_pair;
+    self.bold = old_bold;
+    self.underline = old_underline;
+    self.move_rc(old_row, old_col);
+  }
+
+  /// Shows the cursor's current `(R,C)` and `(X,Y)` positions in the top
+  /// right corner of the window, which helps when debugging coordinate math.
+  /// Like `draw_overlay`, this doesn't disturb the cursor or text style.
+  ///
+  /// This only exists with the `debug-overlay` feature, so calls to it can't
+  /// be accidentally left in a release that doesn't turn the feature on.
+  ///
+  /// ```rust
+  /// # #[cfg(feature = "debug-overlay")]
+  /// # {
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(3, 7);
+  /// easy.debug_overlay();
+  /// assert_eq!(easy.get_cursor_rc(), (3, 7));
+  /// # }
+  /// ```
+  #[cfg(feature = "debug-overlay")]
+  pub fn debug_overlay(&mut self) {
+    let (row, col) = self.get_cursor_rc();
+    let (x, y) = self.get_cursor_xy();
+    let text = format!("RC {},{} XY {},{}", row, col, x, y);
+    self.draw_overlay(|easy| {
+      let (_, col_count) = easy.get_row_col_count();
+      let start = (col_count - text.chars().count() as i32).max(0);
+      easy.put_str_clipped(0, start, &text, col_count);
+    });
+  }
+
+  /// Gives access to the inner pancurses `Window`, for calling something that
+  /// `EasyCurses` doesn't offer itself.
+  ///
+  /// Like the `win` field, this is a last resort. Changing settings through
+  /// the window directly goes around the settings that `EasyCurses` keeps
+  /// track of (such as the current color pair, or echo), so those can end up
+  /// out of date.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count, col_count) = easy.window().get_max_yx();
+  /// assert_eq!((row_count, col_count), easy.get_row_col_count());
+  /// ```
+  pub fn window(&self) -> &pancurses::Window {
+    &self.win
+  }
+
+  /// Gives mutable access to the inner pancurses `Window`, with the same
+  /// cautions as `window`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.window_mut().mvaddch(0, 0, 'z');
+  /// assert_eq!(easy.window().mvinch(0, 0) & 0xFF, 'z' as u32);
+  /// ```
+  pub fn window_mut(&mut self) -> &mut pancurses::Window {
+    &mut self.win
+  }
+
+  /// On Win32 systems this allows you to set the title of the PDcurses
+  /// window. On other systems this does nothing at all.
+  pub fn set_title_win32(&mut self, title: &str) {
+    pancurses::set_title(title);
+  }
+
+  /// Attempts to assign a new cursor visibility. If this is successful you
+  /// get a `Some` back with the old setting inside. If this fails you get a
+  /// `None` back. For more info see
+  /// [curs_set](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/curs_set.html)
+  pub fn set_cursor_visibility(&mut self, vis: CursorVisibility) -> Option<CursorVisibility> {
+    use CursorVisibility::*;
+    let result = pancurses::curs_set(match vis {
+      Invisible => 0,
+      Visible => 1,
+      HighlyVisible => 2,
+    });
+    let old_vis = match result {
+      0 => Some(Invisible),
+      1 => Some(Visible),
+      2 => Some(HighlyVisible),
+      _ => None,
+    };
+    if old_vis.is_some() {
+      self.cursor_visibility = vis;
+    }
+    old_vis
+  }
+
+  /// Attempts to assign the desired cursor visibility, falling back to the
+  /// closest mode that the terminal does support, and gives back the mode
+  /// that actually got set.
+  ///
+  /// Fallbacks go toward a less conspicuous cursor first. `HighlyVisible`
+  /// falls back to `Visible` and then `Invisible`. `Visible` falls back to
+  /// `Invisible`, and only then to `HighlyVisible`. If no mode can be set at
+  /// all then the visibility is unchanged and you get back whatever
+  /// easycurses last successfully set (`Visible` to start with).
+  ///
+  /// ```rust
+  /// use easycurses::{CursorVisibility, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let actual = easy.set_cursor_visibility_best(CursorVisibility::HighlyVisible);
+  /// easy.print(format!("The cursor ended up {:?}", actual));
+  /// ```
+  pub fn set_cursor_visibility_best(&mut self, desired: CursorVisibility) -> CursorVisibility {
+    first_supported_visibility(desired, |vis| self.set_cursor_visibility(vis).is_some()).unwrap_or(self.cursor_visibility)
+  }
+
+  /// Gives the cursor visibility, as of the last successful change to it.
+  pub fn get_cursor_visibility(&self) -> CursorVisibility {
+    self.cursor_visibility
+  }
+
+  /// Hides the cursor while the closure runs, and then puts the cursor
+  /// visibility back the way it was. Drawing with the cursor hidden keeps it
+  /// from flickering around the screen.
+  ///
+  /// ```rust
+  /// use easycurses::{CursorVisibility, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_cursor_visibility(CursorVisibility::Visible);
+  /// let before = easy.get_cursor_visibility();
+  /// easy.with_hidden_cursor(|easy| {
+  ///     easy.print("drawing");
+  /// });
+  /// assert_eq!(easy.get_cursor_visibility(), before);
+  /// ```
+  pub fn with_hidden_cursor<R, F: FnOnce(&mut EasyCurses) -> R>(&mut self, f: F) -> R {
+    let old_vis = self.cursor_visibility;
+    self.set_cursor_visibility(CursorVisibility::Invisible);
+    let result = f(self);
+    self.set_cursor_visibility(old_vis);
+    result
+  }
+
+  /// The terminal gets input from the user. Then it's sometimes buffered up. At
+  /// some point it's passed into the program's input buffer, and then
+  /// `get_input` gets things out of that buffer.
+  ///
+  /// * Character: Input is passed in 1 character at a time, but special
+  ///   characters (such as Ctrl+C and Ctrl+S) are automatically processed for
+  ///   you by the terminal.
   /// * Cooked: Input is passed in 1 line at a time, with the special character
   ///   processing mentioned above enabled.
   /// * RawCharacter: Input is passed in 1 character at a time, and special
   ///   character sequences are not processed automatically.
-  /// * RawCooked: Input is passed in 1 line at a time, and special
-  ///   character sequences are not processed automatically.
+  /// * RawCooked: Input would be passed in 1 line at a time, with special
+  ///   character sequences not processed automatically. Curses has no way to
+  ///   do this (raw mode always means character at a time input), so this
+  ///   mode doesn't change anything and you always get `false`.
+  ///
+  /// The default mode is inherited from the terminal that started the program
+  /// (usually Cooked), so you should _always_ set the desired mode explicitly
+  /// at the start of your program.
+  ///
+  /// See also the [Input
+  /// Mode](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/intov.html#tag_001_005_002)
+  /// section of the curses documentation.
+  ///
+  /// ```rust
+  /// use easycurses::*;
+  ///
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(!easy.set_input_mode(InputMode::RawCooked));
+  /// ```
+  pub fn set_input_mode(&mut self, mode: InputMode) -> bool {
+    to_bool(match mode {
+      InputMode::Character => pancurses::cbreak(),
+      InputMode::Cooked => pancurses::nocbreak(),
+      InputMode::RawCharacter => pancurses::raw(),
+      InputMode::RawCooked => return false,
+    })
+  }
+
+  /// Sets the input mode just like `set_input_mode`, and then discards all
+  /// pending input (as `flush_input` does).
+  ///
+  /// Input that was buffered up under the old mode can otherwise show up
+  /// unexpectedly after switching, such as a whole line of cooked input
+  /// arriving after switching to a character mode. Be aware that this drops
+  /// _any_ input that's waiting, including things pushed with `un_get_input`.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input, InputMode, TimeoutMode};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_input_timeout(TimeoutMode::Immediate);
+  /// easy.un_get_input(Input::Character('a'));
+  /// easy.set_input_mode_clean(InputMode::RawCharacter);
+  /// assert_eq!(easy.get_input(), None);
+  /// ```
+  pub fn set_input_mode_clean(&mut self, mode: InputMode) -> bool {
+    let success = self.set_input_mode(mode);
+    self.flush_input();
+    success
+  }
+
+  /// This controls how long `get_input` will wait before returning a `None`
+  /// value.
+  ///
+  /// The default mode is an unlimited wait.
+  ///
+  /// The `WaitUpTo` value is measured in milliseconds, and any negative value
+  /// is treated as 0 (the same as an immediate timeout).
+  ///
+  /// See also: The
+  /// [notimeout](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/notimeout.html)
+  /// curses function.
+  pub fn set_input_timeout(&mut self, mode: TimeoutMode) {
+    match mode {
+      TimeoutMode::Immediate => self.win.timeout(0),
+      TimeoutMode::WaitUpTo(n) => self.win.timeout(n.max(0)),
+      TimeoutMode::Never => self.win.timeout(-1),
+    };
+    self.input_timeout = mode;
+  }
+
+  /// Enables special key processing from buttons such as the keypad and arrow
+  /// keys. This defaults to `false`. You probably want to set it to `true`.
+  /// If it's not on and the user presses a special key then get_key will
+  /// return will do nothing or give `ERR`.
+  pub fn set_keypad_enabled(&mut self, use_keypad: bool) -> bool {
+    let success = to_bool(self.win.keypad(use_keypad));
+    if success {
+      self.keypad = use_keypad;
+    }
+    success
+  }
+
+  /// Checks if special key processing is on, as of the last successful
+  /// `set_keypad_enabled`.
+  pub fn is_keypad_enabled(&self) -> bool {
+    self.keypad
+  }
+
+  /// Runs the closure with special key processing turned on or off, and then
+  /// puts the setting back the way it was. This is handy for briefly reading
+  /// the raw escape sequences of special keys.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_keypad_enabled(true);
+  /// let inside = easy.with_keypad(false, |easy| easy.is_keypad_enabled());
+  /// assert!(!inside);
+  /// assert!(easy.is_keypad_enabled());
+  /// ```
+  pub fn with_keypad<R, F: FnOnce(&mut EasyCurses) -> R>(&mut self, on: bool, f: F) -> R {
+    let old_keypad = self.keypad;
+    self.set_keypad_enabled(on);
+    let result = f(self);
+    self.set_keypad_enabled(old_keypad);
+    result
+  }
+
+  /// Enables or disables the automatic echoing of input into the window as
+  /// the user types. Default to on, but you probably want it to be off most
+  /// of the time.
+  pub fn set_echo(&mut self, echoing: bool) -> bool {
+    let success = to_bool(if echoing { pancurses::echo() } else { pancurses::noecho() });
+    if success {
+      self.echo = echoing;
+    }
+    success
+  }
+
+  // TODO: pancurses::resize_term?
+
+  /// Checks if the current terminal supports the use of colors.
+  pub fn is_color_terminal(&self) -> bool {
+    self.color_support
+  }
+
+  /// Checks if the terminal can show the full 256 color palette, which is
+  /// needed for `set_color_pair_256`.
+  pub fn is_256_color_terminal(&self) -> bool {
+    self.color_support && pancurses::COLORS() >= 256
+  }
+
+  /// Sets the current color pair to one from the 256 color palette. Each new
+  /// combination is set up with curses the first time it's used, so only the
+  /// pairs that you actually use take up pair ids.
+  ///
+  /// Does nothing and gives `false` if the terminal isn't a 256 color
+  /// terminal, or if there's no room for another pair (there's room for
+  /// about 190 different pairs, fewer if `define_gradient` is used). The color
+  /// remap table doesn't apply to these pairs, and `set_foreground` and
+  /// `set_background_color` keep working from the last basic `ColorPair`.
+  ///
+  /// ```rust
+  /// use easycurses::{Color256, ColorPair256, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let orange = ColorPair256::new(Color256(208), Color256(16));
+  /// assert_eq!(easy.set_color_pair_256(orange), easy.is_256_color_terminal());
+  /// ```
+  pub fn set_color_pair_256(&mut self, pair: ColorPair256) -> bool {
+    if !self.is_256_color_terminal() {
+      return false;
+    }
+    let is_new = !self.pairs_256.pairs.contains_key(&pair);
+    let pair_id = match self.pairs_256.get_or_alloc(pair, pancurses::COLOR_PAIRS()) {
+      Some(pair_id) => pair_id,
+      None => return false,
+    };
+    if is_new && !to_bool(pancurses::init_pair(pair_id, i16::from(pair.fg.0), i16::from(pair.bg.0))) {
+      self.pairs_256.pairs.remove(&pair);
+      return false;
+    }
+    to_bool(self.win.color_set(pair_id))
+  }
+
+  /// Sets the current color pair of the window. Output at any location will
+  /// use this pair until a new pair is set. Does nothing if the terminal does
+  /// not support colors in the first place. The pair is translated through
+  /// the color remap table first, see `set_color_remap`. When pairs are set
+  /// up as they're used (see `initialize_system_lazy`) and there's no room
+  /// for another pair, the current pair is kept.
+  pub fn set_color_pair(&mut self, pair: ColorPair) {
+    if self.color_support {
+      let remapped = remap_pair(&self.color_remap, pair);
+      if let Some(pair_id) = self.pair_id_to_select(remapped) {
+        self.color_pair = Some(pair);
+        self.win.color_set(pair_id);
+      }
+    }
+  }
+
+  /// Sets up a table that translates colors before they're used, which lets
+  /// you swap in an alternate palette (such as a colorblind friendly one)
+  /// without changing all of the places that pick colors.
+  ///
+  /// The table is indexed by color, in the same order as
+  /// `Color::color_iterator`, and each entry gives the color to actually use
+  /// in place of that color. From then on `set_color_pair` and
+  /// `set_color_pair_checked` translate both the foreground and background of
+  /// every pair through the table.
+  ///
+  /// All of the color pairs were already set up at initialization, so
+  /// remapping only changes which of those pairs gets selected. No pairs are
+  /// redefined, and text that's already been drawn keeps its colors.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// use easycurses::Color::*;
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// // Show red as magenta, leaving everything else alone.
+  /// easy.set_color_remap([Black, Magenta, Green, Yellow, Blue, Magenta, Cyan, White]);
+  /// easy.set_color_pair(ColorPair::new(Red, Black));
+  /// easy.print("This is actually magenta.");
+  /// ```
+  pub fn set_color_remap(&mut self, remap: [Color; 8]) {
+    self.color_remap = remap;
+  }
+
+  /// Like `set_color_pair`, but first checks that the pair was actually set
+  /// up when curses was initialized. Some terminals report fewer pairs than
+  /// easycurses needs, and selecting a pair that never got initialized gives
+  /// garbage colors. In that case (or if the terminal has no color at all)
+  /// the current pair is left alone and you get `false` back.
+  pub fn set_color_pair_checked(&mut self, pair: ColorPair) -> bool {
+    let remapped = remap_pair(&self.color_remap, pair);
+    match self.resolve_pair(remapped) {
+      Some(pair_id) if to_bool(self.win.color_set(pair_id)) => {
+        self.color_pair = Some(pair);
+        true
+      }
+      _ => false,
+    }
+  }
+
+  /// Finds the curses pair id to select for a pair, or `None` if the pair was
+  /// never set up. When pairs are set up lazily this sets the pair up if it
+  /// hasn't been already.
+  fn resolve_pair(&mut self, pair: ColorPair) -> Option<i16> {
+    match self.pair_cache {
+      None => {
+        if pair_is_initialized(self.initialized_pairs, pair.0) {
+          Some(pair.0)
+        } else {
+          None
+        }
+      }
+      Some(ref mut cache) => {
+        let (fg, bg) = pair.basic_colors()?;
+        if let Some(pair_id) = cache.get(fg, bg) {
+          return Some(pair_id);
+        }
+        if !self.color_support {
+          return None;
+        }
+        let pair_id = cache.get_or_alloc(fg, bg, pancurses::COLOR_PAIRS())?;
+        if to_bool(pancurses::init_pair(pair_id, color_to_i16(fg), color_to_i16(bg))) {
+          Some(pair_id)
+        } else {
+          // The id was the last one handed out, so giving it back keeps the
+          // ids in order.
+          cache.pairs.remove(&(fg, bg));
+          None
+        }
+      }
+    }
+  }
+
+  /// The pair id that `set_color_pair` selects for a pair. Without lazy
+  /// pairs an uninitialized basic pair still falls back to its usual id, as
+  /// do custom pairs such as gradients. Lazy ids are handed out in order of
+  /// use though, so a basic pair that couldn't get one has no id to fall
+  /// back to and you get `None`.
+  fn pair_id_to_select(&mut self, pair: ColorPair) -> Option<i16> {
+    match self.resolve_pair(pair) {
+      Some(pair_id) => Some(pair_id),
+      None if self.pair_cache.is_some() && pair.basic_colors().is_some() => None,
+      None => Some(pair.0),
+    }
+  }
+
+  /// Changes just the foreground color, keeping the background color of the
+  /// current color pair. This selects the color pair with the new combination
+  /// in the same way as `set_color_pair_checked`, including giving `false` if
+  /// that pair can't be used. The current pair also has to be one made with
+  /// `ColorPair::new`, so this gives `false` while a gradient pair is in use.
+  /// Before any pair is set, the current pair counts as `ColorPair::default()`
+  /// (White on Black), which is what curses shows at startup.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let startup = easy.get_attributes();
+  /// assert_eq!(startup.color_pair(), ColorPair::default());
+  /// if easy.set_foreground(Color::Yellow) {
+  ///     easy.print("y");
+  ///     assert_eq!(easy.dump_styled()[0][0].1, ColorPair::new(Color::Yellow, Color::Black));
+  /// }
+  /// // Going back to the startup style really selects pair 0 again.
+  /// assert!(easy.set_attributes(startup));
+  /// assert_eq!(easy.win.attrget().1, 0);
+  /// easy.move_rc(0, 0);
+  /// easy.set_color_pair(ColorPair::new(Color::Red, Color::Blue));
+  /// if easy.set_foreground(Color::Green) {
+  ///     easy.print("x");
+  ///     assert_eq!(easy.dump_styled()[0][0].1, ColorPair::new(Color::Green, Color::Blue));
+  /// }
+  /// ```
+  pub fn set_foreground(&mut self, fg: Color) -> bool {
+    match self.color_pair.unwrap_or_default().basic_colors() {
+      Some((_, bg)) => self.set_color_pair_checked(ColorPair::new(fg, bg)),
+      None => false,
+    }
+  }
+
+  /// Changes just the background color, keeping the foreground color of the
+  /// current color pair. This works just like `set_foreground`.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_color_pair(ColorPair::new(Color::Red, Color::Blue));
+  /// if easy.set_background_color(Color::Yellow) {
+  ///     easy.print("x");
+  ///     assert_eq!(easy.dump_styled()[0][0].1, ColorPair::new(Color::Red, Color::Yellow));
+  /// }
+  /// ```
+  pub fn set_background_color(&mut self, bg: Color) -> bool {
+    match self.color_pair.unwrap_or_default().basic_colors() {
+      Some((fg, _)) => self.set_color_pair_checked(ColorPair::new(fg, bg)),
+      None => false,
+    }
+  }
+
+  /// Checks if the terminal lets colors be redefined, which is needed for
+  /// `init_custom_color` and `define_gradient`.
+  pub fn can_change_color(&self) -> bool {
+    self.color_support && pancurses::can_change_color()
+  }
+
+  /// Redefines what one of the basic colors looks like. Everything drawn in
+  /// that color, including what's already on screen, takes on the new look.
+  ///
+  /// The red, green, and blue amounts go from 0 to 1000, which is the range
+  /// that curses uses (not the usual 0 to 255, see `init_custom_color_rgb`
+  /// for that). Any amount over 1000 gives `false` without changing anything,
+  /// as does a terminal where `can_change_color` is `false`.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let changeable = easy.can_change_color();
+  /// assert_eq!(easy.init_custom_color(Color::Blue, 0, 300, 1000), changeable);
+  /// assert!(!easy.init_custom_color(Color::Blue, 0, 0, 1001));
+  /// ```
+  pub fn init_custom_color(&mut self, color: Color, r: u16, g: u16, b: u16) -> bool {
+    if !self.can_change_color() || r > 1000 || g > 1000 || b > 1000 {
+      return false;
+    }
+    to_bool(pancurses::init_color(color_to_i16(color), r as i16, g as i16, b as i16))
+  }
+
+  /// Redefines what one of the basic colors looks like, like
+  /// `init_custom_color`, but with the red, green, and blue amounts going from
+  /// 0 to 255. They're scaled to the 0 to 1000 range of curses.
+  pub fn init_custom_color_rgb(&mut self, color: Color, r: u8, g: u8, b: u8) -> bool {
+    let scale = |component| curses_color_component(component) as u16;
+    self.init_custom_color(color, scale(r), scale(g), scale(b))
+  }
+
+  /// Defines a smooth gradient of `steps` custom colors going from `from` to
+  /// `to`, which are given as `(red, green, blue)`. You get back one color
+  /// pair per step, each with the gradient color as the background and white
+  /// as the foreground, ready for use with `set_color_pair`. This is handy for
+  /// things like heatmaps.
+  ///
+  /// This needs a terminal that allows colors to be redefined and has enough
+  /// room for the extra colors and pairs, including any pairs already taken
+  /// by `set_color_pair_256`. If it doesn't, or if curses rejects any of the
+  /// new colors, you get `None`. Each call reuses the same color
+  /// slots, so defining a new gradient changes the colors of any previous
+  /// gradient.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// if let Some(pairs) = easy.define_gradient((0, 0, 255), (255, 0, 0), 8) {
+  ///     assert_eq!(pairs.len(), 8);
+  ///     for pair in pairs {
+  ///         easy.set_color_pair(pair);
+  ///         easy.print(" ");
+  ///     }
+  /// }
+  /// ```
+  pub fn define_gradient(&mut self, from: (u8, u8, u8), to: (u8, u8, u8), steps: usize) -> Option<Vec<ColorPair>> {
+    let old_gradient_pairs = self.pairs_256.gradient_pairs;
+    if !self.color_support
+      || !pancurses::can_change_color()
+      || !gradient_fits(pancurses::COLORS(), pancurses::COLOR_PAIRS(), steps)
+      || !self.pairs_256.reserve_gradient(steps)
+    {
+      return None;
+    }
+    let mut pairs = Vec::with_capacity(steps);
+    for (i, (r, g, b)) in gradient_colors(from, to, steps).into_iter().enumerate() {
+      let color_id = GRADIENT_FIRST_COLOR + i as i16;
+      let pair_id = GRADIENT_FIRST_PAIR + i as i16;
+      let r = curses_color_component(r);
+      let g = curses_color_component(g);
+      let b = curses_color_component(b);
+      if !to_bool(pancurses::init_color(color_id, r, g, b))
+        || !to_bool(pancurses::init_pair(pair_id, pancurses::COLOR_WHITE, color_id))
+      {
+        // No gradient was made, so only the ids of earlier ones stay reserved.
+        self.pairs_256.gradient_pairs = old_gradient_pairs;
+        return None;
+      }
+      pairs.push(ColorPair(pair_id));
+    }
+    Some(pairs)
+  }
+
+  /// Turns an attribute on or off for all future output.
+  ///
+  /// ```rust
+  /// use easycurses::{Attribute, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.set_attribute(Attribute::Reverse, true));
+  /// easy.print("x");
+  /// assert!(easy.set_attribute(Attribute::Reverse, false));
+  /// easy.print("y");
+  /// assert_eq!(easy.dump_styled()[0][0].2, vec![Attribute::Reverse]);
+  /// assert!(easy.dump_styled()[0][1].2.is_empty());
+  /// ```
+  pub fn set_attribute(&mut self, attribute: Attribute, on: bool) -> bool {
+    let bits = attribute_to_chtype(attribute);
+    let success = to_bool(if on { self.win.attron(bits) } else { self.win.attroff(bits) });
+    if success {
+      match attribute {
+        Attribute::Bold => self.bold = on,
+        Attribute::Underline => self.underline = on,
+        _ => (),
+      }
+    }
+    success
+  }
+
+  /// Enables or disables bold text for all future input. This is shorthand
+  /// for `set_attribute(Attribute::Bold, bold_on)`.
+  pub fn set_bold(&mut self, bold_on: bool) -> bool {
+    self.set_attribute(Attribute::Bold, bold_on)
+  }
+
+  /// Checks if bold text is on, as of the last successful `set_bold`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(!easy.is_bold());
+  /// easy.set_bold(true);
+  /// assert!(easy.is_bold());
+  /// let toggled = !easy.is_bold();
+  /// easy.set_bold(toggled);
+  /// assert!(!easy.is_bold());
+  /// ```
+  pub fn is_bold(&self) -> bool {
+    self.bold
+  }
+
+  /// Enables or disables underlined text for all future input. This is
+  /// shorthand for `set_attribute(Attribute::Underline, underline_on)`.
+  pub fn set_underline(&mut self, underline_on: bool) -> bool {
+    self.set_attribute(Attribute::Underline, underline_on)
+  }
+
+  /// Checks if underlined text is on, as of the last successful
+  /// `set_underline`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_underline(true);
+  /// assert!(easy.is_underline());
+  /// easy.set_underline(false);
+  /// assert!(!easy.is_underline());
+  /// ```
+  pub fn is_underline(&self) -> bool {
+    self.underline
+  }
+
+  /// Enables or disables half-bright text for all future output. This is
+  /// shorthand for `set_attribute(Attribute::Dim, dim_on)`.
+  pub fn set_dim(&mut self, dim_on: bool) -> bool {
+    self.set_attribute(Attribute::Dim, dim_on)
+  }
+
+  /// Enables or disables swapping the foreground and background colors for
+  /// all future output. This is shorthand for
+  /// `set_attribute(Attribute::Reverse, reverse_on)`.
+  pub fn set_reverse(&mut self, reverse_on: bool) -> bool {
+    self.set_attribute(Attribute::Reverse, reverse_on)
+  }
+
+  /// Enables or disables blinking text for all future output. This is
+  /// shorthand for `set_attribute(Attribute::Blink, blink_on)`.
+  pub fn set_blink(&mut self, blink_on: bool) -> bool {
+    self.set_attribute(Attribute::Blink, blink_on)
+  }
+
+  /// Enables or disables italic text for all future output. This is
+  /// shorthand for `set_attribute(Attribute::Italic, italic_on)`.
+  pub fn set_italic(&mut self, italic_on: bool) -> bool {
+    self.set_attribute(Attribute::Italic, italic_on)
+  }
+
+  /// Saves the current text style, meaning every attribute that's on plus
+  /// the color pair, so that it can be put back later with `set_attributes`.
+  /// This is the usual "save, change, restore" pattern for drawing nested
+  /// pieces of a display.
+  ///
+  /// ```rust
+  /// use easycurses::{Attribute, Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_bold(true);
+  /// let saved = easy.get_attributes();
+  /// easy.set_bold(false);
+  /// easy.set_reverse(true);
+  /// easy.set_color_pair(ColorPair::new(Color::Red, Color::Blue));
+  /// assert!(easy.set_attributes(saved));
+  /// assert!(easy.is_bold());
+  /// assert_eq!(easy.get_attributes(), saved);
+  /// assert!(!easy.get_attributes().contains(Attribute::Reverse));
+  /// ```
+  ///
+  /// The pair that was really selected is saved, so this also works while a
+  /// pair from `set_color_pair_256` is in use:
+  ///
+  /// ```rust
+  /// use easycurses::{Color, Color256, ColorPair, ColorPair256, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// if easy.set_color_pair_256(ColorPair256::new(Color256(208), Color256(16))) {
+  ///     let saved = easy.get_attributes();
+  ///     let (_, pair_id) = easy.win.attrget();
+  ///     easy.set_color_pair(ColorPair::new(Color::Red, Color::Blue));
+  ///     assert!(easy.set_attributes(saved));
+  ///     assert_eq!(easy.win.attrget().1, pair_id);
+  /// }
+  /// ```
+  pub fn get_attributes(&self) -> Attributes {
+    let (bits, pair_id) = self.win.attrget();
+    Attributes {
+      bits: bits & !pancurses::A_COLOR,
+      pair: self.color_pair.unwrap_or_default(),
+      pair_id,
+    }
+  }
+
+  /// Puts back a text style saved with `get_attributes`, replacing all of the
+  /// current attributes and the color pair.
+  pub fn set_attributes(&mut self, attrs: Attributes) -> bool {
+    let success = to_bool(self.win.attrset(attrs.bits));
+    self.bold = attrs.contains(Attribute::Bold);
+    self.underline = attrs.contains(Attribute::Underline);
+    self.color_pair = if attrs.pair_id == 0 { None } else { Some(attrs.pair) };
+    self.win.color_set(attrs.pair_id);
+    success
+  }
+
+  /// Returns the number of rows and columns available in the window. Each of
+  /// these are the number of locations in that dimension, but the rows and
+  /// cols (as well as the Xs and Ys if you care to use that coordinate space)
+  /// use 0-based indexing, so the actual addressable locations are numbered 0
+  /// to N-1, similar to with slices, `.len()`, and indexing. Fortunately, the
+  /// normal rust Range type already handles this for us. If you wanted to
+  /// iterate every cell of the window you'd probably use a loop like this:
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count,col_count) = easy.get_row_col_count();
+  /// // using RC coordinates.
+  /// for row in 0..row_count {
+  ///     for col in 0..col_count {
+  ///         easy.move_rc(row,col);
+  ///         let (actual_row,actual_col) = easy.get_cursor_rc();
+  ///         assert!(actual_row == row && actual_col == col);
+  ///     }
+  /// }
+  /// // using XY coordinates.
+  /// for y in 0..row_count {
+  ///     for x in 0..col_count {
+  ///         easy.move_xy(x,y);
+  ///         let (actual_x,actual_y) = easy.get_cursor_xy();
+  ///         assert!(actual_x == x && actual_y == y);
+  ///     }
+  /// }
+  /// ```
+  pub fn get_row_col_count(&self) -> (i32, i32) {
+    self.win.get_max_yx()
+  }
+
+  /// Checks that the window is at least `min_rows` by `min_cols`. If it's
+  /// too small you get the current `(rows, cols)` back as the error, so that
+  /// you can ask the user to enlarge their terminal and check again after
+  /// the next `Input::KeyResize`.
+  ///
+  /// ```rust
+  /// let easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count, col_count) = easy.get_row_col_count();
+  /// assert_eq!(easy.require_size(row_count, col_count), Ok(()));
+  /// assert_eq!(easy.require_size(row_count + 1, 1), Err((row_count, col_count)));
+  /// ```
+  pub fn require_size(&self, min_rows: i32, min_cols: i32) -> Result<(), (i32, i32)> {
+    check_min_size(self.get_row_col_count(), min_rows, min_cols)
+  }
+
+  /// Divides the window into a grid of `rows` by `cols` equally sized
+  /// rectangles, given back in row-major order. When the window doesn't
+  /// divide evenly, the leftover rows and columns go to the grid cells at the
+  /// top and left. The grid is based on the window's current size, so call
+  /// this again after a resize.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count, col_count) = easy.get_row_col_count();
+  /// let grid = easy.layout_grid(2, 2);
+  /// assert_eq!(grid.len(), 4);
+  /// assert_eq!(grid[0].row_count + grid[2].row_count, row_count);
+  /// assert_eq!(grid[0].col_count + grid[1].col_count, col_count);
+  /// ```
+  pub fn layout_grid(&mut self, rows: usize, cols: usize) -> Vec<Rect> {
+    let (row_count, col_count) = self.get_row_col_count();
+    grid_rects(Rect::new(0, 0, row_count, col_count), rows, cols)
+  }
+
+  /// Moves the virtual cursor to the row and column specified, relative to
+  /// the top left ("notepad" space). Does not move the terminal's displayed
+  /// cursor (if any) until `refresh` is also called. Out of bounds locations
+  /// cause this command to be ignored.
+  pub fn move_rc(&mut self, row: i32, col: i32) -> bool {
+    to_bool(self.win.mv(row, col))
+  }
+
+  /// Moves the cursor to the given row and column and gives back a
+  /// [`CursorPen`] for chaining drawing operations from there.
+  ///
+  /// [`CursorPen`]: struct.CursorPen.html
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let score = ColorPair::new(Color::Green, Color::Black);
+  /// let worked = easy.at(2, 4).write("Score:").write_colored(" 42", score).write_char('!').done();
+  /// assert!(worked);
+  /// assert_eq!(easy.win.mvinch(2, 4) & 0xFF, 'S' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 10) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 11) & 0xFF, '4' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 13) & 0xFF, '!' as u32);
+  /// ```
+  pub fn at(&mut self, row: i32, col: i32) -> CursorPen<'_> {
+    let success = self.move_rc(row, col);
+    CursorPen { easy: self, success }
+  }
+
+  /// Sets the viewport, which is where the window sits within the world
+  /// coordinates used by `print_world`. It starts out at `(0, 0)`.
+  pub fn set_viewport(&mut self, viewport: Viewport) {
+    self.viewport = viewport;
+  }
+
+  /// Gives the current viewport, see `set_viewport`.
+  pub fn get_viewport(&self) -> Viewport {
+    self.viewport
+  }
+
+  /// Moves the viewport by the given number of rows and columns. Positive
+  /// values move the view down and to the right over the world. The offsets
+  /// stop at the ends of the `i32` range rather than wrapping.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.scroll_viewport(i32::MAX, 3);
+  /// easy.scroll_viewport(1, -1);
+  /// assert_eq!(easy.get_viewport().offset_row, i32::MAX);
+  /// assert_eq!(easy.get_viewport().offset_col, 2);
+  /// ```
+  pub fn scroll_viewport(&mut self, d_row: i32, d_col: i32) {
+    self.viewport.offset_row = self.viewport.offset_row.saturating_add(d_row);
+    self.viewport.offset_col = self.viewport.offset_col.saturating_add(d_col);
+  }
+
+  /// Prints a single line of text at a position in world coordinates, which
+  /// are translated through the viewport to find where it goes in the
+  /// window. Whatever part of the text falls outside of the window is
+  /// clipped off, and text that's entirely off screen is simply skipped. The
+  /// text doesn't wrap. The cursor is put back where it was afterward.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Viewport};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_viewport(Viewport { offset_row: 100, offset_col: 50 });
+  /// assert!(easy.print_world(101, 48, "abcde"));
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'c' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 2) & 0xFF, 'e' as u32);
+  /// easy.scroll_viewport(-1, 0);
+  /// assert!(easy.print_world(101, 51, "xy"));
+  /// assert_eq!(easy.win.mvinch(2, 1) & 0xFF, 'x' as u32);
+  /// assert!(easy.print_world(0, 0, "off screen"));
+  /// ```
+  pub fn print_world(&mut self, world_row: i32, world_col: i32, text: &str) -> bool {
+    let (row, col) = self.viewport.to_screen(world_row, world_col);
+    let (row_count, col_count) = self.get_row_col_count();
+    if row < 0 || row >= row_count {
+      return true;
+    }
+    let (skip, take, start) = clip_span(col, text.chars().count() as i32, col_count);
+    let (old_row, old_col) = self.get_cursor_rc();
+    let mut success = true;
+    for (i, c) in text.chars().skip(skip as usize).take(take as usize).enumerate() {
+      success &= self.put_char_at(row, start + i as i32, c);
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Obtains the cursor's current position using `(R,C)` coordinates
+  /// relative to the top left corner.
+  pub fn get_cursor_rc(&self) -> (i32, i32) {
+    self.win.get_cur_yx()
+  }
+
+  /// The row that the cursor is on, counted from the top.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(2, 0);
+  /// easy.print("Hello");
+  /// assert_eq!(easy.row(), 2);
+  /// assert_eq!(easy.column(), 5);
+  /// assert!(!easy.at_line_end());
+  /// ```
+  pub fn row(&self) -> i32 {
+    self.win.get_cur_y()
+  }
+
+  /// The column that the cursor is on, counted from the left.
+  pub fn column(&self) -> i32 {
+    self.win.get_cur_x()
+  }
+
+  /// Checks if the cursor is in the last column of the window.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (_, col_count) = easy.get_row_col_count();
+  /// easy.move_rc(0, col_count - 2);
+  /// easy.print("a");
+  /// assert!(easy.at_line_end());
+  /// ```
+  pub fn at_line_end(&mut self) -> bool {
+    self.column() == self.win.get_max_x() - 1
+  }
+
+  /// Moves the virtual cursor to the x and y specified, relative to the
+  /// bottom left ("cartesian" space). Does not move the terminal's displayed
+  /// cursor (if any) until `refresh` is also called. Out of bounds locations
+  /// cause this command to be ignored.
+  pub fn move_xy(&mut self, x: i32, y: i32) -> bool {
+    let row_count = self.win.get_max_y();
+    to_bool(self.win.mv(row_count - (y + 1), x))
+  }
+
+  /// Moves the cursor the given number of steps in a direction, stopping at
+  /// the edge of the window. Gives back the new `(R,C)` position.
+  ///
+  /// ```rust
+  /// use easycurses::{Direction, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let (row_count, col_count) = easy.get_row_col_count();
+  /// let (mid_row, mid_col) = (row_count / 2, col_count / 2);
+  /// for &dir in [Direction::Up, Direction::Down, Direction::Left, Direction::Right].iter() {
+  ///     easy.move_rc(mid_row, mid_col);
+  ///     let (d_row, d_col) = dir.delta();
+  ///     assert_eq!(easy.move_in_direction(dir, 1), (mid_row + d_row, mid_col + d_col));
+  /// }
+  /// easy.move_rc(mid_row, mid_col);
+  /// assert_eq!(easy.move_in_direction(Direction::Up, row_count), (0, mid_col));
+  /// ```
+  pub fn move_in_direction(&mut self, dir: Direction, steps: i32) -> (i32, i32) {
+    let (row, col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let (new_row, new_col) = step_within(row, col, dir, steps, row_count, col_count);
+    self.move_rc(new_row, new_col);
+    self.get_cursor_rc()
+  }
+
+  /// Obtains the cursor's current position using `(X,Y)` coordinates relative
+  /// to the bottom left corner.
+  pub fn get_cursor_xy(&self) -> (i32, i32) {
+    let row_count = self.win.get_max_y();
+    let (row, col) = self.win.get_cur_yx();
+    (col, row_count - (row + 1))
+  }
+
+  /// Reads back the character shown at the given `(R,C)` position, without
+  /// its color pair or attributes. The cursor stays where it was. Gives
+  /// `None` if the position is outside the window or the cell doesn't hold a
+  /// valid `char`.
+  ///
+  /// Line drawing characters from `constants::acs` read back as the plain
+  /// character that the terminal's alternate character set draws them with,
+  /// not as the shape on screen.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_bold(true);
+  /// easy.print_at_rc(2, 3, "@");
+  /// easy.move_rc(0, 0);
+  /// assert_eq!(easy.get_char_at_rc(2, 3), Some('@'));
+  /// assert_eq!(easy.get_cursor_rc(), (0, 0));
+  /// assert_eq!(easy.get_char_at_rc(-1, 0), None);
+  /// ```
+  // `chtype` isn't a `u32` on every platform, so the cast is needed elsewhere.
+  #[allow(clippy::unnecessary_cast)]
+  pub fn get_char_at_rc(&self, row: i32, col: i32) -> Option<char> {
+    let (row_count, col_count) = self.get_row_col_count();
+    if row < 0 || row >= row_count || col < 0 || col >= col_count {
+      return None;
+    }
+    let (old_row, old_col) = self.win.get_cur_yx();
+    let ch = self.win.mvinch(row, col);
+    self.win.mv(old_row, old_col);
+    std::char::from_u32((ch & CHARTEXT_MASK) as u32)
+  }
+
+  /// Reads back the character shown at the given `(X,Y)` position, like
+  /// `get_char_at_rc`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.print_char_at_xy(4, 0, '#');
+  /// assert_eq!(easy.get_char_at_xy(4, 0), Some('#'));
+  /// assert_eq!(easy.get_char_at_xy(0, i32::MAX), None);
+  /// assert_eq!(easy.get_char_at_xy(0, i32::MIN), None);
+  /// ```
+  pub fn get_char_at_xy(&self, x: i32, y: i32) -> Option<char> {
+    let row_count = self.win.get_max_y();
+    let row = y.checked_add(1).and_then(|y1| row_count.checked_sub(y1))?;
+    self.get_char_at_rc(row, x)
+  }
+
+  /// When scrolling is enabled, any attempt to move off the bottom margin
+  /// will cause lines within the scrolling region to scroll up one line. If a
+  /// scrolling region is set but scrolling is not enabled then attempts to go
+  /// off the bottom will just print nothing instead. Use `set_scroll_region`
+  /// to control the size of the scrolling region.
+  pub fn set_scrolling(&mut self, scrolling: bool) -> bool {
+    to_bool(self.win.scrollok(scrolling))
+  }
+
+  /// Sets the top and bottom margins of the scrolling region. The inputs
+  /// should be the line numbers (relative to the top of the screen) for the
+  /// borders. Either border can be 0.
+  ///
+  /// See also:
+  /// [setscrreg](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/setscrreg.html)
+  pub fn set_scroll_region(&mut self, top: i32, bottom: i32) -> bool {
+    let success = to_bool(self.win.setscrreg(top, bottom));
+    if success {
+      self.scroll_region_bottom = Some(bottom);
+    }
+    success
+  }
+
+  /// Sets if `append_line` follows the end of the log, like `tail -f`. This is
+  /// on by default. Turn it off while the user is scrolled back through older
+  /// lines, and back on once they return to the bottom.
+  ///
+  /// The crate only tracks this flag and does the scrolling for new lines.
+  /// Keeping the lines themselves, so that older ones can be shown and the
+  /// view can be redrawn when following resumes, is up to your program.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_scrolling(true);
+  /// easy.set_scroll_region(0, 2);
+  /// easy.append_line("one");
+  /// easy.set_follow_tail(false);
+  /// assert!(!easy.is_following_tail());
+  /// assert!(easy.append_line("two"));
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'o' as u32);
+  /// easy.set_follow_tail(true);
+  /// easy.append_line("three");
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 'o' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 't' as u32);
+  /// ```
+  pub fn set_follow_tail(&mut self, on: bool) {
+    self.follow_tail = on;
+  }
+
+  /// Checks if `append_line` is following the end of the log, see
+  /// `set_follow_tail`.
+  pub fn is_following_tail(&self) -> bool {
+    self.follow_tail
+  }
+
+  /// Adds a line of text to the end of a streaming log, and then refreshes
+  /// so that the line shows up right away.
+  ///
+  /// The text goes on the bottom line of the scrolling region and is then
+  /// followed by a newline, which scrolls everything in the region up by one
+  /// line. That leaves the newest line just above the bottom line, which is
+  /// kept blank for the next one. Scrolling has to be enabled (see
+  /// `set_scrolling`), and if no scrolling region was set the whole window is
+  /// used.
+  ///
+  /// While following the tail is off (see `set_follow_tail`), the line isn't
+  /// drawn at all, so that whatever the user scrolled back to stays put.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_scrolling(true);
+  /// easy.set_scroll_region(0, 2);
+  /// for line in &["one", "two", "three", "four"] {
+  ///     assert!(easy.append_line(line));
+  /// }
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 't' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, 'h' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'f' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 0) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(3, 0) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn append_line(&mut self, text: &str) -> bool {
+    if !self.follow_tail {
+      return true;
+    }
+    let (row_count, _) = self.get_row_col_count();
+    let bottom = self.scroll_region_bottom.unwrap_or(row_count - 1);
+    self.move_rc(bottom, 0) && self.print(text) && self.print("\n") && self.refresh()
+  }
+
+  /// Prints the given string-like value into the window by printing each
+  /// individual character into the window. If there is any error encountered
+  /// upon printing a character, that cancels the printing of the rest of the
+  /// characters.
+  ///
+  /// If a left margin is set (see `set_left_margin`), each new line (whether
+  /// from a `'\n'` or from wrapping) starts at the margin column.
+  ///
+  /// If tab expansion is on (see `set_tab_expansion`), tabs are turned into
+  /// spaces before printing. Control characters are handled according to
+  /// `set_control_char_policy` before that.
+  ///
+  /// If an output encoding other than UTF-8 is set (see
+  /// `set_output_encoding`), the text is converted to that encoding first.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let text = "The quick brown fox jumps over the lazy dog.";
+  /// easy.print(text);
+  /// easy.move_rc(1, 0);
+  /// for c in text.chars() {
+  ///     easy.print_char(c);
+  /// }
+  /// for col in 0..text.len() as i32 {
+  ///     assert_eq!(easy.win.mvinch(0, col), easy.win.mvinch(1, col));
+  /// }
+  /// // Format characters and nuls are printed like anything else.
+  /// easy.move_rc(2, 0);
+  /// assert!(easy.print("100%s\0"));
+  /// assert_eq!(easy.win.mvinch(2, 3) & 0xFF, '%' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 4) & 0xFF, 's' as u32);
+  /// ```
+  pub fn print<S: AsRef<str>>(&mut self, asref: S) -> bool {
+    let text = apply_control_char_policy(asref.as_ref(), self.control_char_policy);
+    let text = match self.tab_expansion {
+      Some(tab_width) => Cow::Owned(expand_tabs(&text, self.column(), self.left_margin, tab_width)),
+      None => text,
+    };
+    // Here we want to
+    if self.output_encoding != OutputEncoding::Utf8 {
+      let bytes: Vec<u8> = text.chars().map(|c| encode_legacy_char(self.output_encoding, c)).collect();
+      let margin = self.left_margin > 0;
+      bytes.into_iter().all(|b| {
+        let ch = pancurses::chtype::from(b);
+        if margin {
+          self.print_char_within_margin(ch)
+        } else {
+          self.print_char(ch)
+        }
+      })
+    } else if self.left_margin > 0 {
+      text.chars().all(|c| self.print_char_within_margin(c))
+    } else if (cfg!(windows) && !text.is_ascii()) || text.contains('\0') {
+      // PDCurses does an extra intermediate CString allocation for non-ASCII
+      // text, so we just print out each character one at a time to avoid
+      // that. A string with a nul in it can't be made into a CString at all,
+      // so that has to go one character at a time too.
+      text.chars().all(|c| self.print_char(c))
+    } else {
+      // This has to be `addstr` and not `printw`, since `printw` treats the
+      // text as a C format string and would act on any `%` in it.
+      to_bool(self.win.addstr(&text))
+    }
+  }
+
+  /// Sets the encoding that `print` converts text to before handing it to
+  /// curses. This is for consoles that expect a legacy code page, such as a
+  /// Windows console that isn't set to UTF-8, where printing non-ASCII text
+  /// as UTF-8 comes out garbled. Characters that the encoding doesn't have
+  /// are printed as `?`. The default is `Utf8`, which leaves text alone.
+  ///
+  /// This only changes the bytes that are sent to the terminal. Strings that
+  /// you pass in are still normal UTF-8, and other output such as
+  /// `print_char` isn't converted.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, OutputEncoding};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_output_encoding(OutputEncoding::Cp437);
+  /// // This sends the single byte 0x82, which is é in code page 437.
+  /// assert!(easy.print("é"));
+  /// ```
+  pub fn set_output_encoding(&mut self, encoding: OutputEncoding) {
+    self.output_encoding = encoding;
+  }
+
+  /// Sets what `print` does with control characters, which is handy when
+  /// printing text from an untrusted source. The default is `Raw`, which
+  /// hands them to curses as-is.
+  ///
+  /// ```rust
+  /// use easycurses::{ControlCharPolicy, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_control_char_policy(ControlCharPolicy::Caret);
+  /// easy.print("a\x07b");
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, '^' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 2) & 0xFF, 'G' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 3) & 0xFF, 'b' as u32);
+  /// easy.set_control_char_policy(ControlCharPolicy::Strip);
+  /// easy.move_rc(1, 0);
+  /// easy.print("c\x1bd");
+  /// assert_eq!(easy.win.mvinch(1, 1) & 0xFF, 'd' as u32);
+  /// ```
+  pub fn set_control_char_policy(&mut self, policy: ControlCharPolicy) {
+    self.control_char_policy = policy;
+  }
+
+  /// Sets `print` to expand each tab into spaces itself, out to the next tab
+  /// stop, with tab stops every `spaces` columns. Curses implementations
+  /// don't all agree on how to expand tabs (PDCurses and ncurses differ), so
+  /// this gives the same output everywhere. Pass `None` to go back to handing
+  /// tabs to curses as-is, which is the default.
+  ///
+  /// Tab stops are counted from the left edge of the window. Text that wraps
+  /// at the edge of the window isn't accounted for.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_tab_expansion(Some(4));
+  /// easy.print("a\tb\n\tc");
+  /// assert_eq!(easy.win.mvinch(0, 4) & 0xFF, 'b' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 4) & 0xFF, 'c' as u32);
+  /// ```
+  pub fn set_tab_expansion(&mut self, spaces: Option<u8>) {
+    self.tab_expansion = spaces;
+  }
+
+  /// Prints spaces out to the first tab stop at or after column `col`,
+  /// staying on the current row, which lines text up into columns without a
+  /// full table. Tab stops use the width from `set_tab_expansion`, or every
+  /// 8 columns when tab expansion is off. A tab stop past the right edge is
+  /// clamped to the last column. Gives `false` without moving if the cursor
+  /// is already past that column.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.print("ab");
+  /// assert!(easy.tab_to(3));
+  /// easy.print("1");
+  /// easy.move_rc(1, 0);
+  /// easy.print("abcde");
+  /// assert!(easy.tab_to(3));
+  /// easy.print("2");
+  /// assert_eq!(easy.win.mvinch(0, 8) & 0xFF, '1' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 8) & 0xFF, '2' as u32);
+  /// ```
+  pub fn tab_to(&mut self, col: i32) -> bool {
+    let tab_width = self.tab_expansion.map_or(DEFAULT_TAB_WIDTH, i32::from);
+    let (_, col_count) = self.get_row_col_count();
+    let target = next_tab_stop(col, tab_width).min(col_count - 1);
+    let current = self.column();
+    if current > target {
+      return false;
+    }
+    let spaces: String = (current..target).map(|_| ' ').collect();
+    to_bool(self.win.addstr(&spaces))
+  }
+
+  /// Prints a sequence of `(text, pair)` spans, each in its own color pair,
+  /// such as the output of a syntax highlighter. The color pair is only
+  /// switched when it actually changes from one span to the next, and the
+  /// color pair that was active beforehand is restored at the end. Printing
+  /// stops at the first span that fails.
+  ///
+  /// ```rust
+  /// use easycurses::{Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let keyword = ColorPair::new(Color::Yellow, Color::Black);
+  /// let ident = ColorPair::new(Color::Cyan, Color::Black);
+  /// assert!(easy.print_spans(&[("fn", keyword), (" main", ident)]));
+  /// if easy.is_color_terminal() {
+  ///     let cells = easy.dump_styled();
+  ///     assert_eq!(cells[0][0].1, keyword);
+  ///     assert_eq!(cells[0][1].1, keyword);
+  ///     assert_eq!(cells[0][3].1, ident);
+  /// }
+  /// ```
+  pub fn print_spans(&mut self, spans: &[(&str, ColorPair)]) -> bool {
+    let (_, old_pair_id) = self.win.attrget();
+    let old_pair = self.color_pair;
+    let mut current = None;
+    let mut success = true;
+    for &(text, pair) in spans {
+      if current != Some(pair) {
+        self.set_color_pair(pair);
+        current = Some(pair);
+      }
+      if !self.print(text) {
+        success = false;
+        break;
+      }
+    }
+    self.win.color_set(old_pair_id);
+    self.color_pair = old_pair;
+    success
+  }
+
+  /// Prints the given character into the window.
+  pub fn print_char<T: ToChtype>(&mut self, character: T) -> bool {
+    to_bool(self.win.addch(character))
+  }
+
+  /// Prints a line drawing character into the window, like `print_char`.
+  ///
+  /// ```rust
+  /// use easycurses::constants::acs::AcsChar;
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_acs_char(AcsChar::ulcorner()));
+  /// assert!(easy.move_rc(0, 0));
+  /// assert!(easy.insert_acs_char(AcsChar::hline()));
+  /// assert_eq!(easy.win.mvinch(0, 0), AcsChar::hline().raw());
+  /// assert_eq!(easy.win.mvinch(0, 1), AcsChar::ulcorner().raw());
+  /// ```
+  pub fn print_acs_char(&mut self, character: AcsChar) -> bool {
+    self.print_char(character)
+  }
+
+  /// Moves the cursor to the given `(R,C)` position and prints the string
+  /// there, like `move_rc` followed by `print`. If the move fails nothing is
+  /// printed and you get `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_at_rc(2, 3, "hi"));
+  /// assert_eq!(easy.win.mvinch(2, 4) & 0xFF, 'i' as u32);
+  /// let (row_count, _) = easy.get_row_col_count();
+  /// assert!(!easy.print_at_rc(row_count, 0, "nowhere"));
+  /// ```
+  pub fn print_at_rc<S: AsRef<str>>(&mut self, row: i32, col: i32, asref: S) -> bool {
+    self.move_rc(row, col) && self.print(asref)
+  }
+
+  /// Moves the cursor to the given `(X,Y)` position and prints the string
+  /// there, like `move_xy` followed by `print`. If the move fails nothing is
+  /// printed and you get `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count, _) = easy.get_row_col_count();
+  /// assert!(easy.print_at_xy(3, 0, "hi"));
+  /// assert_eq!(easy.win.mvinch(row_count - 1, 3) & 0xFF, 'h' as u32);
+  /// ```
+  pub fn print_at_xy<S: AsRef<str>>(&mut self, x: i32, y: i32, asref: S) -> bool {
+    self.move_xy(x, y) && self.print(asref)
+  }
+
+  /// Moves the cursor to the given `(R,C)` position and prints the character
+  /// there, like `move_rc` followed by `print_char`. If the move fails nothing
+  /// is printed and you get `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_char_at_rc(1, 1, 'x'));
+  /// assert_eq!(easy.win.mvinch(1, 1) & 0xFF, 'x' as u32);
+  /// ```
+  pub fn print_char_at_rc<T: ToChtype>(&mut self, row: i32, col: i32, character: T) -> bool {
+    self.move_rc(row, col) && self.print_char(character)
+  }
+
+  /// Moves the cursor to the given `(X,Y)` position and prints the character
+  /// there, like `move_xy` followed by `print_char`. If the move fails
+  /// nothing is printed and you get `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let (row_count, _) = easy.get_row_col_count();
+  /// assert!(easy.print_char_at_xy(1, 1, 'x'));
+  /// assert_eq!(easy.win.mvinch(row_count - 2, 1) & 0xFF, 'x' as u32);
+  /// ```
+  pub fn print_char_at_xy<T: ToChtype>(&mut self, x: i32, y: i32, character: T) -> bool {
+    self.move_xy(x, y) && self.print_char(character)
+  }
+
+  /// Prints a character, and then if that put the cursor at the start of a
+  /// new line moves it over to the left margin.
+  fn print_char_within_margin<T: ToChtype>(&mut self, c: T) -> bool {
+    let success = self.print_char(c);
+    if self.column() == 0 {
+      let (row, margin) = (self.row(), self.left_margin);
+      self.move_rc(row, margin);
+    }
+    success
+  }
+
+  /// Sets the column that `print` returns to when it starts a new line,
+  /// whether that's because of a `'\n'` or because the text wrapped at the
+  /// edge of the window. This lets you print indented paragraphs. The default
+  /// margin is 0, the normal curses behavior.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_left_margin(4);
+  /// easy.move_rc(0, 4);
+  /// easy.print("ab\ncd");
+  /// assert_eq!(easy.win.mvinch(1, 4) & 0xFF, 'c' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn set_left_margin(&mut self, col: i32) {
+    self.left_margin = col.max(0);
+  }
+
+  /// Prints text in a field of exactly `width` columns starting at the given
+  /// location, which is handy for forms and tables. Shorter text is padded
+  /// with spaces according to `align`, and longer text is cut off at the end
+  /// of the field. Text is measured with `measure_text`, so combining marks
+  /// don't use up columns. Anything off the edge of the window is skipped.
+  /// The cursor is put back where it was afterward.
+  ///
+  /// A field that would run past the right edge of the window is cut short
+  /// at the edge before the text is fitted into it, so alignment happens
+  /// within the part of the field that can be seen.
+  ///
+  /// ```rust
+  /// use easycurses::{Alignment, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.print("##########");
+  /// assert!(easy.print_field(0, 1, 5, "ab", Alignment::Right));
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 4) & 0xFF, 'a' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 6) & 0xFF, '#' as u32);
+  /// assert!(easy.print_field(1, 0, 3, "overlong", Alignment::Center));
+  /// assert_eq!(easy.win.mvinch(1, 2) & 0xFF, 'e' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 3) & 0xFF, ' ' as u32);
+  /// assert!(easy.print_field(2, 0, i32::MAX, "x", Alignment::Left));
+  /// assert_eq!(easy.win.mvinch(2, 0) & 0xFF, 'x' as u32);
+  /// ```
+  pub fn print_field(&mut self, row: i32, col: i32, width: i32, text: &str, align: Alignment) -> bool {
+    let (_, col_count) = self.get_row_col_count();
+    let width = width.min(col_count.saturating_sub(col));
+    let field = fit_field(text, width, align);
+    let (old_row, old_col) = self.get_cursor_rc();
+    let success = self.put_str_clipped(row, col, &field, col + width);
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Prints text as a clickable hyperlink to `url`, using the OSC 8 escape
+  /// sequence that many modern terminals understand.
+  ///
+  /// Curses can't send escape sequences of its own, so the text is printed
+  /// normally (and the window is refreshed), and then the same text is
+  /// written again directly to the terminal wrapped in the link sequence.
+  /// Terminals that don't support links ignore the sequence and just show
+  /// the text. The rewritten text takes on whatever style the terminal was
+  /// last left in, so links are best printed in the default style. The text
+  /// should fit on one line, since curses' wrapping isn't followed.
+  ///
+  /// Control characters are removed from the text, and a `url` with anything
+  /// other than printable ASCII in it gives `false` without printing, so
+  /// untrusted text and links can't sneak other escape sequences through.
+  /// The sequences are written raw, so a console that doesn't understand
+  /// them at all (such as the Windows console under PDCurses) can show them
+  /// as literal text.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_link("docs", "https://docs.rs/easycurses"));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 'd' as u32);
+  /// assert!(!easy.print_link("docs", "https://docs.rs/\x1b[2J"));
+  /// ```
+  pub fn print_link(&mut self, text: &str, url: &str) -> bool {
+    use std::io::Write;
+    let text = link_text(text);
+    let link = match osc8_link(&text, url) {
+      Some(link) => link,
+      None => return false,
+    };
+    let (row, col) = self.get_cursor_rc();
+    if !(self.print(&text) && self.refresh()) {
+      return false;
+    }
+    // Save the cursor, go to where the text starts, write the link, and then
+    // put the cursor back where curses expects it to be.
+    let out = format!("\x1b7\x1b[{};{}H{}\x1b8", row + 1, col + 1, link);
+    let mut stdout = std::io::stdout();
+    stdout.write_all(out.as_bytes()).and_then(|_| stdout.flush()).is_ok()
+  }
+
+  /// Prints text right-to-left, for scripts such as Hebrew or Arabic. The
+  /// first character of the string goes at `right_col` and each following
+  /// character goes one column further left. Characters that would land off
+  /// the edge of the window are skipped. The cursor is put back where it was
+  /// afterward.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_rtl(0, 5, "abc"));
+  /// assert_eq!(easy.win.mvinch(0, 5) & 0xFF, 'a' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 3) & 0xFF, 'c' as u32);
+  /// assert!(easy.print_rtl(1, 1, "xyz"));
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'y' as u32);
+  /// assert!(easy.print_rtl(i32::MAX, i32::MIN, "abc"));
+  /// assert!(easy.print_rtl(2, i32::MAX, "abc"));
+  /// ```
+  pub fn print_rtl(&mut self, row: i32, right_col: i32, text: &str) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    if row < 0 || row >= row_count {
+      return true;
+    }
+    let (old_row, old_col) = self.get_cursor_rc();
+    let mut success = true;
+    for (i, c) in text.chars().enumerate() {
+      let col = i64::from(right_col) - i as i64;
+      if col < 0 {
+        break;
+      }
+      if col < i64::from(col_count) {
+        success &= self.put_char_at(row, col as i32, c);
+      }
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Draws a single line text field that scrolls sideways to keep a caret
+  /// position on screen.
+  ///
+  /// The field starts at the given row and column and is `width` cells wide.
+  /// Only the part of `text` that fits is drawn, chosen so that the character
+  /// at `cursor_pos` (counted in `char`s, and allowed to be one past the end
+  /// for appending) is visible. Any unused cells are blanked, and the cursor
+  /// is left on the caret position so the terminal cursor shows the caret.
+  ///
+  /// A field that would run past the right edge of the window is cut short
+  /// at the edge, so it never wraps onto the next row.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let text = "The quick brown fox";
+  /// assert!(easy.print_scrolling_field(0, 0, 10, text, text.len()));
+  /// assert_eq!(easy.get_cursor_rc(), (0, 9));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 'b' as u32);
+  /// let (_, col_count) = easy.get_row_col_count();
+  /// easy.print_scrolling_field(1, col_count - 3, i32::MAX, text, 0);
+  /// assert_eq!(easy.win.mvinch(2, 0) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn print_scrolling_field(&mut self, row: i32, col: i32, width: i32, text: &str, cursor_pos: usize) -> bool {
+    if width <= 0 || !self.move_rc(row, col) {
+      return false;
+    }
+    let (_, col_count) = self.get_row_col_count();
+    let chars: Vec<char> = text.chars().collect();
+    let width = width.min(col_count - col) as usize;
+    let start = scrolling_field_start(width, cursor_pos);
+    for i in start..start + width {
+      if !self.print_char(chars.get(i).cloned().unwrap_or(' ')) {
+        return false;
+      }
+    }
+    self.move_rc(row, col + (cursor_pos - start) as i32)
+  }
+
+  /// Draws the outline of a rectangle using the given character, leaving the
+  /// inside of the rectangle untouched. Any part of the outline that's off
+  /// the edge of the window is skipped. The cursor is put back where it was
+  /// afterward.
+  ///
+  /// This uses a plain character instead of line drawing characters, which is
+  /// handy for selection boxes and highlights.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_rect_outline(Rect::new(1, 1, 3, 4), '#'));
+  /// assert_eq!(easy.win.mvinch(1, 1) & 0xFF, '#' as u32);
+  /// assert_eq!(easy.win.mvinch(3, 4) & 0xFF, '#' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 2) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn draw_rect_outline(&mut self, rect: Rect, ch: char) -> bool {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let window = Rect::new(0, 0, row_count, col_count);
+    let mut success = true;
+    for (row, col) in rect.perimeter() {
+      if window.contains(row, col) {
+        success &= self.put_char_at(row, col, ch);
+      }
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Draws a border around the edge of the whole window with the default line
+  /// drawing characters. Gives `false` without drawing anything if the window
+  /// is smaller than 2 by 2, since then there's no room for a border. See
+  /// `draw_box_in` for a box around just part of the window.
+  ///
+  /// ```rust
+  /// use easycurses::EasyCurses;
+  /// use easycurses::constants::acs;
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_box());
+  /// assert_eq!(easy.win.mvinch(0, 0), acs::ulcorner());
+  /// assert_eq!(easy.win.mvinch(1, 0), acs::vline());
+  /// ```
+  pub fn draw_box(&mut self) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    row_count >= 2 && col_count >= 2 && to_bool(self.win.draw_box(0, 0))
+  }
+
+  /// Draws a border around the edge of the whole window like `draw_box`, but
+  /// with the given characters for the sides. The corners use the default
+  /// line drawing characters. Gives `false` without drawing anything if the
+  /// window is smaller than 2 by 2.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_box_with_chars('|', '='));
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, '=' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, '|' as u32);
+  /// ```
+  pub fn draw_box_with_chars(&mut self, vertical: char, horizontal: char) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    row_count >= 2 && col_count >= 2 && to_bool(self.win.draw_box(vertical, horizontal))
+  }
+
+  /// Draws a border around the edge of the whole window, with each of the
+  /// four sides and four corners given separately. These are usually the
+  /// line drawing characters from `constants::acs`, but any characters can be
+  /// mixed in. A `0` for any of them uses that piece's default line drawing
+  /// character.
+  ///
+  /// ```rust
+  /// use easycurses::EasyCurses;
+  /// use easycurses::constants::acs;
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let corner = '*' as u32;
+  /// assert!(easy.draw_border(acs::vline(), acs::vline(), acs::hline(), acs::hline(), corner, corner, corner, corner));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, '*' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1), acs::hline());
+  /// assert_eq!(easy.win.mvinch(1, 0), acs::vline());
+  /// ```
+  #[allow(clippy::too_many_arguments)]
+  pub fn draw_border(
+    &mut self,
+    ls: pancurses::chtype,
+    rs: pancurses::chtype,
+    ts: pancurses::chtype,
+    bs: pancurses::chtype,
+    tl: pancurses::chtype,
+    tr: pancurses::chtype,
+    bl: pancurses::chtype,
+    br: pancurses::chtype,
+  ) -> bool {
+    to_bool(self.win.border(ls, rs, ts, bs, tl, tr, bl, br))
+  }
+
+  /// Draws a horizontal line of `len` copies of `character` to the right,
+  /// starting at the cursor. The cursor doesn't move. A line that would run
+  /// past the right edge is clamped to it, and a negative `len` gives `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(2, 3);
+  /// assert!(easy.draw_hline('-', 4));
+  /// assert_eq!(easy.win.mvinch(2, 6) & 0xFF, '-' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 7) & 0xFF, ' ' as u32);
+  /// assert!(easy.draw_hline('-', i32::MAX));
+  /// assert!(!easy.draw_hline('-', -1));
+  /// ```
+  pub fn draw_hline<T: ToChtype>(&mut self, character: T, len: i32) -> bool {
+    if len < 0 {
+      return false;
+    }
+    let (_, col) = self.get_cursor_rc();
+    let (_, col_count) = self.get_row_col_count();
+    let len = len.min(col_count - col);
+    to_bool(self.win.hline(character, len))
+  }
+
+  /// Draws a vertical line of `len` copies of `character` downward, starting
+  /// at the cursor. The cursor doesn't move. A line that would run past the
+  /// bottom edge is clamped to it, and a negative `len` gives `false`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(2, 3);
+  /// assert!(easy.draw_vline('|', 3));
+  /// assert_eq!(easy.win.mvinch(4, 3) & 0xFF, '|' as u32);
+  /// assert_eq!(easy.win.mvinch(5, 3) & 0xFF, ' ' as u32);
+  /// assert!(!easy.draw_vline('|', -1));
+  /// ```
+  pub fn draw_vline<T: ToChtype>(&mut self, character: T, len: i32) -> bool {
+    if len < 0 {
+      return false;
+    }
+    let (row, _) = self.get_cursor_rc();
+    let (row_count, _) = self.get_row_col_count();
+    let len = len.min(row_count - row);
+    to_bool(self.win.vline(character, len))
+  }
+
+  /// Like `draw_hline`, using the line drawing character from
+  /// `constants::acs`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_hline_acs(5));
+  /// assert_eq!(easy.win.mvinch(0, 4), easycurses::constants::acs::hline());
+  /// ```
+  pub fn draw_hline_acs(&mut self, len: i32) -> bool {
+    self.draw_hline(constants::acs::hline(), len)
+  }
+
+  /// Like `draw_vline`, using the line drawing character from
+  /// `constants::acs`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_vline_acs(5));
+  /// assert_eq!(easy.win.mvinch(4, 0), easycurses::constants::acs::vline());
+  /// ```
+  pub fn draw_vline_acs(&mut self, len: i32) -> bool {
+    self.draw_vline(constants::acs::vline(), len)
+  }
+
+  /// Draws a box around the edge of the rectangle in the given style. Like
+  /// `draw_rect_outline`, any part that's off the edge of the window is
+  /// skipped and the cursor is put back where it was afterward.
+  ///
+  /// ```rust
+  /// use easycurses::{BorderStyle, EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_box_in(Rect::new(0, 0, 3, 4), BorderStyle::Ascii));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, '+' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, '-' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, '|' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 3) & 0xFF, '+' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 1) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn draw_box_in(&mut self, rect: Rect, style: BorderStyle) -> bool {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let success = self.draw_rect_border(rect, style.pieces());
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Draws a box like `draw_box_in`, with a title centered in the top
+  /// border. The title gets a space on either side, and is clipped if it's
+  /// too long to fit between the corners.
+  ///
+  /// ```rust
+  /// use easycurses::{BorderStyle, EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.draw_titled_box(Rect::new(0, 0, 4, 10), "Hi", BorderStyle::Single));
+  /// // The top border is 8 cells between the corners, " Hi " takes 4.
+  /// assert_eq!(easy.win.mvinch(0, 3) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 4) & 0xFF, 'H' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 5) & 0xFF, 'i' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 6) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 0), easycurses::constants::acs::ulcorner());
+  /// assert_eq!(easy.win.mvinch(3, 5), easycurses::constants::acs::hline());
+  /// ```
+  pub fn draw_titled_box(&mut self, rect: Rect, title: &str, style: BorderStyle) -> bool {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let mut success = self.draw_rect_border(rect, style.pieces());
+    let inner_width = rect.col_count - 2;
+    if inner_width > 0 && !title.is_empty() {
+      let label: Vec<char> = format!(" {} ", title).chars().take(inner_width as usize).collect();
+      let start = rect.col + 1 + (inner_width - label.len() as i32) / 2;
+      let (row_count, col_count) = self.get_row_col_count();
+      let window = Rect::new(0, 0, row_count, col_count);
+      for (i, &c) in label.iter().enumerate() {
+        let col = start + i as i32;
+        if window.contains(rect.row, col) {
+          success &= self.put_char_at(rect.row, col, c);
+        }
+      }
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Draws a border around the edge of the rectangle, clipped to the window.
+  /// The pieces are the upper left, upper right, lower left, and lower right
+  /// corners, followed by the horizontal and vertical edges.
+  fn draw_rect_border(&mut self, rect: Rect, pieces: [pancurses::chtype; 6]) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    let window = Rect::new(0, 0, row_count, col_count);
+    let bottom = rect.row + rect.row_count - 1;
+    let right = rect.col + rect.col_count - 1;
+    let mut success = true;
+    for (row, col) in rect.perimeter() {
+      if !window.contains(row, col) {
+        continue;
+      }
+      let piece = match (row == rect.row, row == bottom, col == rect.col, col == right) {
+        (true, _, true, _) => pieces[0],
+        (true, _, _, true) => pieces[1],
+        (_, true, true, _) => pieces[2],
+        (_, true, _, true) => pieces[3],
+        (true, _, _, _) | (_, true, _, _) => pieces[4],
+        _ => pieces[5],
+      };
+      success &= self.put_char_at(row, col, piece);
+    }
+    success
+  }
+
+  /// Puts a string at the given location, one column at a time, stopping
+  /// before `end_col` or the edge of the window. Zero width characters and
+  /// the rest of a grapheme cluster (see `measure_text`) are added right after
+  /// the character before them, so that curses can combine them.
+  fn put_str_clipped(&mut self, row: i32, col: i32, text: &str, end_col: i32) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    let window = Rect::new(0, 0, row_count, col_count);
+    let mut success = true;
+    let mut c_col = col;
+    let mut last_drawn = false;
+    for (unit, width) in text_units(text) {
+      if width == 0 {
+        if last_drawn {
+          success &= to_bool(self.win.addstr(unit));
+        }
+        continue;
+      }
+      // Curses gives each code point that isn't zero width a cell of its
+      // own, even within a cluster, so that's how far the unit really goes.
+      let cells = (unit.chars().filter(|&c| char_width(c) > 0).count() as i32).max(1);
+      if c_col + cells > end_col {
+        break;
+      }
+      last_drawn = window.contains(row, c_col);
+      if last_drawn {
+        let mut chars = unit.chars();
+        if let Some(first) = chars.next() {
+          success &= self.put_char_at(row, c_col, first);
+        }
+        if !chars.as_str().is_empty() {
+          success &= to_bool(self.win.addstr(chars.as_str()));
+        }
+      }
+      c_col += cells;
+    }
+    success
+  }
+
+  /// Puts a character at the given location. Unlike a plain move and
+  /// `print_char` this also works in the bottom right cell of the window,
+  /// where curses would otherwise report an error from trying to advance the
+  /// cursor past the end of the window.
+  fn put_char_at<T: ToChtype>(&mut self, row: i32, col: i32, character: T) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    if row == row_count - 1 && col == col_count - 1 {
+      to_bool(self.win.mvinsch(row, col, character))
+    } else {
+      to_bool(self.win.mvaddch(row, col, character))
+    }
+  }
+
+  /// Inserts the character desired at the current location, pushing the
+  /// current character at that location (and all after it on the same line)
+  /// one cell to the right.
+  pub fn insert_char<T: ToChtype>(&mut self, character: T) -> bool {
+    to_bool(self.win.insch(character))
+  }
+
+  /// Inserts a line drawing character at the current location, like
+  /// `insert_char`.
+  pub fn insert_acs_char(&mut self, character: AcsChar) -> bool {
+    self.insert_char(character)
+  }
+
+  /// Deletes the character under the cursor. Characters after it on same the
+  /// line are pulled left one position and the final character cell is left
+  /// blank. The cursor position does not move.
+  pub fn delete_char(&mut self) -> bool {
+    to_bool(self.win.delch())
+  }
+
+  /// Inserts a line above the current line. The bottom line is lost.
+  pub fn insert_line(&mut self) -> bool {
+    to_bool(self.win.insertln())
+  }
+
+  /// Deletes the line under the cursor. Lines below are moved up one line and
+  /// the final line is left blank. The cursor position does not move.
+  pub fn delete_line(&mut self) -> bool {
+    to_bool(self.win.deleteln())
+  }
+
+  /// Inserts a line above the given row, the same as `insert_line` would if
+  /// the cursor were on that row. The cursor is put back where it was
+  /// afterward. Out of bounds rows return `false` and do nothing.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(1, 0);
+  /// easy.print("a");
+  /// easy.move_rc(0, 3);
+  /// assert!(easy.insert_line_at(1));
+  /// assert_eq!(easy.get_cursor_rc(), (0, 3));
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 0) & 0xFF, 'a' as u32);
+  /// assert!(!easy.insert_line_at(-1));
+  /// ```
+  pub fn insert_line_at(&mut self, row: i32) -> bool {
+    self.with_row(row, EasyCurses::insert_line)
+  }
+
+  /// Deletes the given row, the same as `delete_line` would if the cursor were
+  /// on that row. The cursor is put back where it was afterward. Out of bounds
+  /// rows return `false` and do nothing.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.move_rc(2, 0);
+  /// easy.print("b");
+  /// easy.move_rc(0, 0);
+  /// assert!(easy.delete_line_at(1));
+  /// assert_eq!(easy.get_cursor_rc(), (0, 0));
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'b' as u32);
+  /// let (row_count, _) = easy.get_row_col_count();
+  /// assert!(!easy.delete_line_at(row_count));
+  /// ```
+  pub fn delete_line_at(&mut self, row: i32) -> bool {
+    self.with_row(row, EasyCurses::delete_line)
+  }
+
+  /// Moves to the given row (keeping the current column), runs the operation,
+  /// and then restores the cursor.
+  fn with_row<F: FnOnce(&mut Self) -> bool>(&mut self, row: i32, op: F) -> bool {
+    let (row_count, _) = self.get_row_col_count();
+    if row < 0 || row >= row_count {
+      return false;
+    }
+    let (old_row, old_col) = self.get_cursor_rc();
+    let result = self.move_rc(row, old_col) && op(self);
+    self.move_rc(old_row, old_col);
+    result
+  }
+
+  /// For positive n, insert n lines into the specified window above the current
+  /// line. The n bottom lines are lost. For negative n, delete n lines
+  /// (starting with the one under the cursor), and move the remaining lines up.
+  /// The bottom n lines are cleared. The current cursor position remains the
+  /// same.
+  pub fn bulk_insert_delete_line(&mut self, n: i32) -> bool {
+    to_bool(self.win.insdelln(n))
+  }
+
+  /// Clears the entire screen.
+  ///
+  /// **Note:** This function can cause flickering of the output with PDCurses
+  /// if you're clearing the screen and then immediately writing to the whole
+  /// screen before you end up calling `refresh`. The exact location of the
+  /// flickering effect seems to vary from machine to machine. If you intend
+  /// to simply replace the whole window with new content, just overwrite the
+  /// previous values without calling `clear` and things will be fine.
+  pub fn clear(&mut self) -> bool {
+    to_bool(self.win.clear())
+  }
+
+  /// Clears just the given rectangle, blanking it with the window's
+  /// background the same way `clear` would. Any part of the rectangle that's
+  /// off the edge of the window is skipped, and the cursor is put back where
+  /// it was afterward.
+  ///
+  /// Since only the rectangle is touched, this doesn't have the flickering
+  /// problem that `clear` can have.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// for row in 0..4 {
+  ///     easy.move_rc(row, 0);
+  ///     easy.print("xxxx");
+  /// }
+  /// assert!(easy.clear_region(Rect::new(1, 1, 2, 2)));
+  /// assert_eq!(easy.win.mvinch(1, 1) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(2, 2) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, 'x' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 0) & 0xFF, 'x' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 3) & 0xFF, 'x' as u32);
+  /// assert_eq!(easy.win.mvinch(3, 1) & 0xFF, 'x' as u32);
+  /// ```
+  pub fn clear_region(&mut self, rect: Rect) -> bool {
+    let background = self.win.getbkgd();
+    self.fill_region(rect, background)
+  }
+
+  /// Gives every cell of a rectangle the color pair and attributes given,
+  /// keeping the characters that are already there. This is the usual way to
+  /// highlight a block of the screen, such as a selected row of a table. Any
+  /// attributes that the cells had before are replaced, and any part of the
+  /// rectangle that's off the edge of the window is skipped. The cursor is
+  /// left where it was.
+  ///
+  /// ```rust
+  /// use easycurses::{Attribute, Color, ColorPair, EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.print("abcd");
+  /// let pair = ColorPair::new(Color::Yellow, Color::Blue);
+  /// assert!(easy.restyle_region(Rect::new(0, 1, 1, 2), pair, &[Attribute::Reverse]));
+  /// let (character, cell_pair, attributes) = easy.dump_styled()[0][1].clone();
+  /// assert_eq!(character, 'b');
+  /// assert_eq!(attributes, vec![Attribute::Reverse]);
+  /// if easy.is_color_terminal() {
+  ///     assert_eq!(cell_pair, pair);
+  /// }
+  /// assert!(easy.dump_styled()[0][3].2.is_empty());
+  /// ```
+  pub fn restyle_region(&mut self, rect: Rect, pair: ColorPair, attrs: &[Attribute]) -> bool {
+    let pair_id = if self.color_support {
+      let remapped = remap_pair(&self.color_remap, pair);
+      match self.pair_id_to_select(remapped) {
+        Some(pair_id) => pair_id,
+        None => return false,
+      }
+    } else {
+      0
+    };
+    let attr_bits = attrs.iter().fold(0, |bits, &attribute| bits | attribute_to_chtype(attribute));
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let (_, take, start) = clip_span(rect.col, rect.col_count, col_count);
+    let mut success = true;
+    if take > 0 {
+      for row in rect.row.max(0)..(rect.row + rect.row_count).min(row_count) {
+        success &= to_bool(self.win.mvchgat(row, start, take, attr_bits, pair_id));
+      }
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Fills the part of the rectangle that's within the window with the given
+  /// character, restoring the cursor afterward.
+  fn fill_region<T: ToChtype + Copy>(&mut self, rect: Rect, character: T) -> bool {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let area = rect.intersection(Rect::new(0, 0, row_count, col_count));
+    let mut success = true;
+    for row in area.row..area.row + area.row_count {
+      for col in area.col..area.col + area.col_count {
+        success &= self.put_char_at(row, col, character);
+      }
+    }
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Refreshes the window's appearance on the screen. With some
+  /// implementations you don't need to call this, the screen will refresh
+  /// itself on its own. However, for portability, you should call this at the
+  /// end of each draw cycle.
+  ///
+  /// If a bell was requested with `request_bell`, it's played now.
+  pub fn refresh(&mut self) -> bool {
+    if self.bell_requested {
+      self.bell_requested = false;
+      self.bell();
+    }
+    to_bool(self.win.refresh())
+  }
+
+  /// Refreshes the window like `refresh` does, but if that fails it assumes
+  /// that the terminal was resized out from under curses. In that case the
+  /// window is resized to match the terminal (`resize(0, 0)`), marked to be
+  /// redrawn in full, and then the refresh is tried one more time. The return
+  /// value is if the refresh ultimately worked. Only a single retry is made.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.print("Hello");
+  /// easy.resize(10, 20);
+  /// assert!(easy.refresh_resilient());
+  /// ```
+  pub fn refresh_resilient(&mut self) -> bool {
+    refresh_with_retry(|retry| {
+      if retry {
+        self.resize(0, 0);
+        self.win.touch();
+      }
+      self.refresh()
+    })
+  }
+
+  /// Reads back the entire window, giving the character, color pair, and
+  /// attributes of every cell. The outer `Vec` holds rows from top to bottom
+  /// and each inner `Vec` holds that row's cells from left to right.
+  ///
+  /// This is handy for checking your drawing in tests, or for exporting the
+  /// screen to some other format. Cells that never had a color pair set
+  /// report the default pair. The cursor is put back where it was afterward.
+  ///
+  /// ```rust
+  /// use easycurses::{Attribute, Color, ColorPair, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let pair = ColorPair::new(Color::Green, Color::Black);
+  /// easy.set_color_pair(pair);
+  /// easy.set_underline(true);
+  /// easy.print("x");
+  /// let (character, cell_pair, attributes) = easy.dump_styled()[0][0].clone();
+  /// assert_eq!(easy.get_cursor_rc(), (0, 1));
+  /// assert_eq!(character, 'x');
+  /// assert!(attributes.contains(&Attribute::Underline));
+  /// if easy.is_color_terminal() {
+  ///     assert_eq!(cell_pair, pair);
+  /// }
+  /// ```
+  pub fn dump_styled(&mut self) -> Vec<Vec<(char, ColorPair, Vec<Attribute>)>> {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let cells: Vec<Vec<_>> = (0..row_count)
+      .map(|row| (0..col_count).map(|col| decode_chtype(self.win.mvinch(row, col))).collect())
+      .collect();
+    // mvinch moves the cursor as it goes.
+    self.move_rc(old_row, old_col);
+    match self.pair_cache {
+      // Lazy pair ids are handed out in order of use, so they have to be
+      // looked up to find the colors.
+      Some(ref cache) => cells
+        .into_iter()
+        .map(|row| {
+          row
+            .into_iter()
+            .map(|(c, pair, attributes)| {
+              let pair = cache.colors(pair.0).map_or(pair, |(fg, bg)| ColorPair::new(fg, bg));
+              (c, pair, attributes)
+            })
+            .collect()
+        })
+        .collect(),
+      None => cells,
+    }
+  }
+
+  /// Plays an audible beep if possible, if not the screen is flashed. If
+  /// neither is available then nothing happens. Also does nothing while quiet
+  /// mode is on, see `set_quiet`.
+  pub fn beep(&mut self) {
+    self.bell_count = self.bell_count.saturating_add(1);
+    if !self.quiet {
+      pancurses::beep();
+    }
+  }
+
+  /// Flashes the screen if possible, if not an audible beep is played. If
+  /// neither is available then nothing happens. Also does nothing while quiet
+  /// mode is on, see `set_quiet`.
+  pub fn flash(&mut self) {
+    self.bell_count = self.bell_count.saturating_add(1);
+    if !self.quiet {
+      pancurses::flash();
+    }
+  }
+
+  /// Flashes the screen by showing it in reverse video for the given number
+  /// of milliseconds. Unlike `flash`, which is often so quick that it's easy
+  /// to miss, this gives a flash that's reliably visible, which makes it a
+  /// good visual bell.
+  ///
+  /// **Note:** This blocks for the full `ms` milliseconds.
+  ///
+  /// The flash is done by temporarily changing the window's background, and
+  /// the previous background is put back afterward. Like `flash`, this does
+  /// nothing while quiet mode is on.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let background = easy.win.getbkgd();
+  /// easy.flash_for(10);
+  /// assert_eq!(easy.win.getbkgd(), background);
+  /// ```
+  pub fn flash_for(&mut self, ms: i32) {
+    self.bell_count = self.bell_count.saturating_add(1);
+    if self.quiet {
+      return;
+    }
+    let background = self.win.getbkgd();
+    self.win.bkgd(background ^ pancurses::A_REVERSE);
+    self.refresh();
+    pancurses::napms(ms);
+    self.win.bkgd(background);
+    self.refresh();
+  }
+
+  /// Highlights just the edge of the window in reverse video for 150
+  /// milliseconds, and then puts it back the way it was. This is a visual
+  /// bell that's much easier on photosensitive users than `flash` or
+  /// `flash_for`, which invert the whole screen.
+  ///
+  /// **Note:** This blocks for the full 150 milliseconds. Like the other
+  /// bells, this does nothing while quiet mode is on.
+  ///
+  /// ```rust
+  /// use easycurses::{Attribute, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.print("x");
+  /// easy.gentle_bell();
+  /// let (character, _, attributes) = easy.dump_styled()[0][0].clone();
+  /// assert_eq!(character, 'x');
+  /// assert!(!attributes.contains(&Attribute::Reverse));
+  /// ```
+  pub fn gentle_bell(&mut self) {
+    self.bell_count = self.bell_count.saturating_add(1);
+    if self.quiet {
+      return;
+    }
+    let (row_count, col_count) = self.get_row_col_count();
+    let edges = [
+      Rect::new(0, 0, 1, col_count),
+      Rect::new(row_count - 1, 0, 1, col_count),
+      Rect::new(0, 0, row_count, 1),
+      Rect::new(0, col_count - 1, row_count, 1),
+    ];
+    let saved: Vec<RegionSnapshot> = edges.iter().map(|&edge| self.capture_region(edge)).collect();
+    for snapshot in &saved {
+      for &(row, col, ch) in &snapshot.cells {
+        self.put_char_at(row, col, ch ^ pancurses::A_REVERSE);
+      }
+    }
+    self.win.refresh();
+    pancurses::napms(GENTLE_BELL_MS);
+    for snapshot in &saved {
+      self.restore_region(snapshot);
+    }
+    self.win.refresh();
+  }
+
+  /// Rings the bell in whichever way `set_bell_mode` picked, which is a
+  /// `beep` unless you've changed it.
+  pub fn bell(&mut self) {
+    match self.bell_mode {
+      BellMode::Beep => self.beep(),
+      BellMode::Flash => self.flash(),
+      BellMode::Gentle => self.gentle_bell(),
+    }
+  }
+
+  /// Sets what kind of bell `bell` and `request_bell` give. This lets users
+  /// who can't hear a beep, or who are sensitive to flashing, pick a bell
+  /// that suits them.
+  ///
+  /// ```rust
+  /// use easycurses::{BellMode, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_bell_mode(BellMode::Gentle);
+  /// assert_eq!(easy.get_bell_mode(), BellMode::Gentle);
+  /// easy.bell();
+  /// assert_eq!(easy.take_bell_count(), 1);
+  /// ```
+  pub fn set_bell_mode(&mut self, mode: BellMode) {
+    self.bell_mode = mode;
+  }
+
+  /// Gives the bell mode that was last set with `set_bell_mode`.
+  pub fn get_bell_mode(&self) -> BellMode {
+    self.bell_mode
+  }
+
+  /// Asks for a `bell` to be played at the next `refresh`, instead of right
+  /// away. This only sets a flag, so it doesn't call into curses at all, which
+  /// makes it the safe choice from places where calling curses is a problem.
+  /// Any number of requests between two refreshes give just a single bell.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_quiet(true);
+  /// easy.request_bell();
+  /// easy.request_bell();
+  /// assert_eq!(easy.take_bell_count(), 0);
+  /// easy.refresh();
+  /// assert_eq!(easy.take_bell_count(), 1);
+  /// easy.refresh();
+  /// assert_eq!(easy.take_bell_count(), 0);
+  /// ```
+  pub fn request_bell(&mut self) {
+    self.bell_requested = true;
+  }
+
+  /// Turns quiet mode on or off. While quiet mode is on, `beep`, `flash`,
+  /// `flash_for`, and `gentle_bell` do nothing at all. This is a hard
+  /// override that takes precedence over any other bell setting, and it's
+  /// intended for test suites and headless environments where bells are just
+  /// noise. Quiet mode starts off.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_quiet(true);
+  /// assert!(easy.is_quiet());
+  /// easy.beep();
+  /// easy.flash();
+  /// ```
+  pub fn set_quiet(&mut self, quiet: bool) {
+    self.quiet = quiet;
+  }
+
+  /// Checks if quiet mode is on, see `set_quiet`.
+  pub fn is_quiet(&self) -> bool {
+    self.quiet
+  }
+
+  /// Gives back how many times `beep`, `flash`, `flash_for`, or
+  /// `gentle_bell` have been called since the last time you checked, and
+  /// resets the count to zero. Calls made while quiet mode is on are counted
+  /// too.
+  ///
+  /// This is mostly intended for testing, so that you can check that bad
+  /// input got some feedback without having to listen for it.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_quiet(true);
+  /// // Input is read back last in first out.
+  /// easy.un_get_input(Input::Character('\n'));
+  /// easy.un_get_input(Input::Character('a'));
+  /// easy.un_get_input(Input::Character('7'));
+  /// let text = easy.read_validated(0, 0, 5, |c| c.is_ascii_digit());
+  /// assert_eq!(text, Some("7".to_string()));
+  /// assert_eq!(easy.take_bell_count(), 1);
+  /// assert_eq!(easy.take_bell_count(), 0);
+  /// ```
+  pub fn take_bell_count(&mut self) -> u32 {
+    let count = self.bell_count;
+    self.bell_count = 0;
+    count
+  }
+
+  /// Gets an `Input` from the curses input buffer. This will block or not
+  /// according to the input mode, see `set_input_mode`. If `KeyResize` is
+  /// seen and `auto_resize` is enabled then the window will automatically
+  /// update its size for you. In that case, `KeyResize` is still passed to
+  /// you so that you can change anything else that might need to be updated.
+  ///
+  /// Dragging the corner of a terminal can send dozens of `KeyResize` events
+  /// at once, so when auto resizing any other `KeyResize` events that are
+  /// already waiting directly behind the first one are merged into it. You
+  /// get one `KeyResize` and there's only one resize. Only consecutive queued
+  /// events are merged, a resize that arrives after some other input will be
+  /// delivered separately. Merging can be turned off with
+  /// `set_resize_coalescing`.
+  ///
+  /// Typed characters outside of ASCII, such as `é`, come back as a single
+  /// `Input::Character`. On unix the terminal sends them as several UTF-8
+  /// bytes, and pancurses reads the whole sequence and decodes it before
+  /// handing it over, so there's nothing to put back together. A sequence
+  /// that isn't valid UTF-8 comes back as `Input::Unknown` with its first
+  /// byte.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input, TimeoutMode};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_input_timeout(TimeoutMode::Immediate);
+  /// // Pushed input comes back out in reverse order.
+  /// easy.un_get_input(Input::Character('x'));
+  /// easy.un_get_input(Input::KeyResize);
+  /// easy.un_get_input(Input::KeyResize);
+  /// easy.un_get_input(Input::KeyResize);
+  /// let mut inputs = Vec::new();
+  /// while let Some(input) = easy.get_input() {
+  ///     inputs.push(input);
+  /// }
+  /// // Three resizes went in, but only one came out.
+  /// assert_eq!(inputs, vec![Input::KeyResize, Input::Character('x')]);
+  /// ```
+  pub fn get_input(&mut self) -> Option<pancurses::Input> {
+    let ret = self.win.getch();
+    if self.auto_resize {
+      if let Some(Input::KeyResize) = ret {
+        if self.resize_coalescing {
+          self.drain_queued_resizes();
+        }
+        self.resize(0, 0);
+      }
+    }
+    ret
+  }
+
+  /// Sets if `get_input` merges a burst of queued `KeyResize` events into one.
+  /// This is on by default. Turn it off if you need to see every `KeyResize`
+  /// individually, each one still resizes the window.
+  ///
+  /// Merging only happens as part of auto resizing, so while `auto_resize` is
+  /// off every `KeyResize` is delivered no matter this setting.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input, TimeoutMode};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_input_timeout(TimeoutMode::Immediate);
+  /// easy.set_resize_coalescing(false);
+  /// easy.un_get_input(Input::Character('x'));
+  /// easy.un_get_input(Input::KeyResize);
+  /// easy.un_get_input(Input::KeyResize);
+  /// assert_eq!(easy.get_input(), Some(Input::KeyResize));
+  /// assert_eq!(easy.get_input(), Some(Input::KeyResize));
+  /// assert_eq!(easy.get_input(), Some(Input::Character('x')));
+  /// easy.set_resize_coalescing(true);
+  /// easy.un_get_input(Input::Character('y'));
+  /// easy.un_get_input(Input::KeyResize);
+  /// easy.un_get_input(Input::KeyResize);
+  /// assert_eq!(easy.get_input(), Some(Input::KeyResize));
+  /// assert_eq!(easy.get_input(), Some(Input::Character('y')));
+  /// ```
+  pub fn set_resize_coalescing(&mut self, on: bool) {
+    self.resize_coalescing = on;
+  }
+
+  /// Reads a single input, waiting no longer than the given time. This is the
+  /// basic poll of an event loop. The timeout is only used for this one read,
+  /// afterward the input timeout goes back to whatever `set_input_timeout`
+  /// last set. Since curses measures timeouts in whole milliseconds, the
+  /// duration is rounded down to milliseconds and capped at `i32::MAX` of
+  /// them.
+  ///
+  /// ```rust
+  /// use std::time::{Duration, Instant};
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let start = Instant::now();
+  /// assert_eq!(easy.poll(Duration::from_millis(0)), None);
+  /// assert!(start.elapsed() < Duration::from_secs(1));
+  /// ```
+  pub fn poll(&mut self, timeout: Duration) -> Option<Input> {
+    let old_timeout = self.input_timeout;
+    self.set_input_timeout(TimeoutMode::WaitUpTo(duration_to_timeout_ms(timeout)));
+    let input = self.get_input();
+    self.set_input_timeout(old_timeout);
+    input
+  }
+
+  /// Guesses if a key is being held down, for things like movement in a game.
+  /// Pass in each key as you get it from `get_input`. You get `true` if the
+  /// same key was last passed in no more than `window_ms` milliseconds ago
+  /// (negative values count as 0).
+  ///
+  /// This is only a heuristic: terminals don't report key presses and
+  /// releases, they just send the key over and over while it's held. The
+  /// first event of a hold always counts as a tap, and the delay before the
+  /// terminal starts repeating (often around half a second) is usually longer
+  /// than the time between repeats, so pick `window_ms` with that in mind.
+  pub fn is_key_repeating(&mut self, key: Input, window_ms: i32) -> bool {
+    key_repeats(&mut self.key_last_seen, key, Instant::now(), window_ms)
+  }
+
+  /// Waits for input like `get_input`, but calls `on_idle` each time that
+  /// `idle_ms` milliseconds go by with no input. After the callback runs the
+  /// wait starts over, so this only returns once some input actually arrives.
+  /// This is handy for idle animations or a screensaver.
+  ///
+  /// The callback gets the `EasyCurses` handle, so it can draw and refresh as
+  /// usual. It can also change settings, but the input timeout is put back to
+  /// `idle_ms` after each call, and the timeout from before this method was
+  /// called is restored before returning. Reading input from inside the
+  /// callback works too, but that input is then not returned from here.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let mut idle_calls = 0;
+  /// let input = easy.get_input_idle(0, |easy| {
+  ///     idle_calls += 1;
+  ///     easy.un_get_input(Input::Character('k'));
+  /// });
+  /// assert_eq!(input, Some(Input::Character('k')));
+  /// assert_eq!(idle_calls, 1);
+  /// ```
+  pub fn get_input_idle<F: FnMut(&mut EasyCurses)>(&mut self, idle_ms: i32, mut on_idle: F) -> Option<Input> {
+    let old_timeout = self.input_timeout;
+    let input = loop {
+      self.set_input_timeout(TimeoutMode::WaitUpTo(idle_ms));
+      if let Some(input) = self.get_input() {
+        break input;
+      }
+      on_idle(self);
+    };
+    self.set_input_timeout(old_timeout);
+    Some(input)
+  }
+
+  /// Feeds each input of a recorded session to the handler in order, stopping
+  /// early if the handler gives back `false`. This is for running a recorded
+  /// session as a repeatable test of your input handling.
+  ///
+  /// The recorded input goes straight to the handler. It bypasses `getch`
+  /// entirely, so nothing is read from or pushed onto the real terminal input,
+  /// and none of the `get_input` handling (such as `auto_resize`) happens.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// let recording = [Input::Character('a'), Input::Character('b'), Input::Character('q'), Input::Character('c')];
+  /// let mut typed = String::new();
+  /// easy.replay(&recording, |easy, input| match input {
+  ///     Input::Character('q') => false,
+  ///     Input::Character(c) => {
+  ///         typed.push(c);
+  ///         easy.print_char(c);
+  ///         true
+  ///     }
+  ///     _ => true,
+  /// });
+  /// assert_eq!(typed, "ab");
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, 'b' as u32);
+  /// ```
+  pub fn replay<F: FnMut(&mut EasyCurses, Input) -> bool>(&mut self, recording: &[Input], mut handler: F) {
+    for &input in recording {
+      if !handler(self, input) {
+        break;
+      }
+    }
+  }
+
+  /// Discards any `KeyResize` events waiting at the front of the input queue.
+  /// The first other input found is pushed back so that it's read next.
+  fn drain_queued_resizes(&mut self) {
+    self.win.timeout(0);
+    loop {
+      match self.win.getch() {
+        Some(Input::KeyResize) => (),
+        Some(other) => {
+          self.win.ungetch(&other);
+          break;
+        }
+        None => break,
+      }
+    }
+    let timeout = self.input_timeout;
+    self.set_input_timeout(timeout);
+  }
+
+  /// Runs a fixed rate frame loop, which is the basic structure of most
+  /// animated programs.
+  ///
+  /// Each frame, all of the input that's waiting is gathered up and passed to
+  /// `frame` along with the time since the previous frame started (zero for
+  /// the first frame). Then the loop sleeps for whatever is left of the frame
+  /// so that there are about `fps` frames per second. A `fps` of 0 means that
+  /// there's no sleeping at all. The loop stops as soon as `frame` returns
+  /// `false`.
+  ///
+  /// The input timeout is set to `Immediate` while the loop runs and is put
+  /// back afterward. The `frame` function should do all of its own drawing,
+  /// including calling `refresh`.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// let mut frames = 0;
+  /// easy.run_loop(60, |easy, inputs, _delta| {
+  ///     frames += 1;
+  ///     easy.print(format!("Frame {} saw {} inputs\n", frames, inputs.len()));
+  ///     easy.refresh();
+  ///     frames < 3
+  /// });
+  /// assert_eq!(frames, 3);
+  /// ```
+  pub fn run_loop<F: FnMut(&mut EasyCurses, &[Input], Duration) -> bool>(&mut self, fps: u32, mut frame: F) {
+    let old_timeout = self.input_timeout;
+    self.set_input_timeout(TimeoutMode::Immediate);
+    let mut timer = FrameTimer::new(fps);
+    let mut inputs = Vec::new();
+    loop {
+      let delta = timer.start_frame(Instant::now());
+      inputs.clear();
+      while let Some(input) = self.get_input() {
+        inputs.push(input);
+      }
+      if !frame(self, &inputs, delta) {
+        break;
+      }
+      sleep(timer.remaining(Instant::now()));
+    }
+    self.set_input_timeout(old_timeout);
+  }
+
+  /// Gets an input from curses as a plain integer key code, for terminals
+  /// with keys that the `Input` type doesn't have a name for. Characters come
+  /// back as their Unicode code point, special keys as the curses `KEY_`
+  /// code, and anything curses couldn't identify as the code it gave.
+  /// Timeouts work the same as with `get_input`.
+  ///
+  /// This skips the `auto_resize` handling that `get_input` does, so if you
+  /// get the resize key code you'll have to call `resize` yourself.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.un_get_input(Input::Character('a'));
+  /// assert_eq!(easy.get_input_raw(), Some('a' as i32));
+  /// ```
+  pub fn get_input_raw(&mut self) -> Option<i32> {
+    self.win.getch().and_then(input_to_key_code)
+  }
+
+  /// Gets an input from curses like `get_input` does, but simplified into an
+  /// [`Event`]: arrow keys become directions, a resize becomes
+  /// `Event::Resize`, and a mouse event becomes `Event::Mouse`. Timeouts work
+  /// the same as with `get_input`.
+  ///
+  /// [`Event`]: enum.Event.html
+  ///
+  /// ```rust
+  /// use easycurses::{Direction, EasyCurses, Event, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.un_get_input(Input::KeyUp);
+  /// assert_eq!(easy.get_event(), Some(Event::Direction(Direction::Up)));
+  /// ```
+  pub fn get_event(&mut self) -> Option<Event> {
+    match self.get_input()? {
+      Input::KeyMouse => Some(self.get_mouse_event().map_or(Event::Other(Input::KeyMouse), Event::Mouse)),
+      input => Some(Event::from(input)),
+    }
+  }
+
+  /// Turns on reporting of the given kinds of mouse events, or turns mouse
+  /// reporting off with `MouseMask::NONE`. Mouse events then arrive from
+  /// `get_input` as `Input::KeyMouse`, after which `get_mouse_event` gives the
+  /// details, or from `get_event` as an `Event::Mouse` with the details
+  /// already filled in. Gives `false` if the terminal can't report any of the
+  /// events that were asked for.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, MouseMask};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// if !easy.enable_mouse(MouseMask::ALL) {
+  ///     easy.print("No mouse support, use the keyboard instead.");
+  /// }
+  /// // Turning reporting off always works.
+  /// assert!(easy.enable_mouse(MouseMask::NONE));
+  /// ```
+  pub fn enable_mouse(&mut self, events: MouseMask) -> bool {
+    let reported = pancurses::mousemask(events.0, std::ptr::null_mut());
+    events == MouseMask::NONE || reported != 0
+  }
+
+  /// Gives the details of the mouse event that was just read as an
+  /// `Input::KeyMouse`, or `None` if there isn't one waiting.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// assert_eq!(easy.get_mouse_event(), None);
+  /// ```
+  pub fn get_mouse_event(&mut self) -> Option<MouseEvent> {
+    pancurses::getmouse().ok().map(|event| MouseEvent::from_curses(event.x, event.y, event.bstate))
+  }
+
+  /// Gets an event like `get_event` does, along with the `Instant` it was
+  /// read, for measuring the time between events to spot double clicks or key
+  /// chords.
+  ///
+  /// The timestamp is taken when the event is read, not when the key was
+  /// physically pressed. Curses doesn't record that, so events that sat
+  /// waiting while the program was busy all get timestamps from when they were
+  /// finally read, close together.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Event, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.un_get_input(Input::Character('b'));
+  /// easy.un_get_input(Input::Character('a'));
+  /// let (first, first_at) = easy.get_event_timed().unwrap();
+  /// let (second, second_at) = easy.get_event_timed().unwrap();
+  /// assert_eq!((first, second), (Event::Character('a'), Event::Character('b')));
+  /// assert!(second_at >= first_at);
+  /// ```
+  pub fn get_event_timed(&mut self) -> Option<(Event, Instant)> {
+    self.get_event().map(|event| (event, Instant::now()))
+  }
+
+  /// Discards all type-ahead that has been input by the user but not yet read
+  /// by the program.
+  pub fn flush_input(&mut self) {
+    pancurses::flushinp();
+  }
+
+  /// Discards the `Character` inputs that are waiting to be read, and keeps
+  /// everything else (special keys, `KeyResize`, and so on) waiting in the same
+  /// order. Unlike `flush_input`, this also looks at inputs that were pushed
+  /// with `un_get_input`, since it works by reading everything that's waiting
+  /// and pushing the rest back.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input, TimeoutMode};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.set_input_timeout(TimeoutMode::Immediate);
+  /// // Input is read back last in first out: a, KeyUp, b, KeyF1.
+  /// easy.un_get_input(Input::KeyF1);
+  /// easy.un_get_input(Input::Character('b'));
+  /// easy.un_get_input(Input::KeyUp);
+  /// easy.un_get_input(Input::Character('a'));
+  /// easy.flush_chars();
+  /// assert_eq!(easy.get_input(), Some(Input::KeyUp));
+  /// assert_eq!(easy.get_input(), Some(Input::KeyF1));
+  /// assert_eq!(easy.get_input(), None);
+  /// ```
+  pub fn flush_chars(&mut self) {
+    self.win.timeout(0);
+    let mut kept = Vec::new();
+    while let Some(input) = self.win.getch() {
+      match input {
+        Input::Character(_) => (),
+        other => kept.push(other),
+      }
+    }
+    // The input stack is last in first out, so push back in reverse.
+    for input in kept.iter().rev() {
+      self.win.ungetch(input);
+    }
+    let timeout = self.input_timeout;
+    self.set_input_timeout(timeout);
+  }
+
+  /// Pushes an `Input` value into the input stack so that it will be returned
+  /// by the next call to `get_input`. The return value is if the operation
+  /// was successful.
+  pub fn un_get_input(&mut self, input: pancurses::Input) -> bool {
+    to_bool(self.win.ungetch(&input))
+  }
+
+  /// Pushes every character of a string into the input stack, so that
+  /// `get_input` gives them back in order as if the user had typed them. This
+  /// is for scripted demos and walkthroughs.
+  ///
+  /// The whole string comes out before anything that was already waiting,
+  /// including earlier `un_get_input` pushes. Curses only has room for so many
+  /// pushed inputs, so very long strings can fail partway, in which case you
+  /// get `false` and only the end of the string is waiting.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.type_string("hi"));
+  /// assert_eq!(easy.get_input(), Some(Input::Character('h')));
+  /// assert_eq!(easy.get_input(), Some(Input::Character('i')));
+  /// ```
+  pub fn type_string(&mut self, text: &str) -> bool {
+    // The input stack is last in first out, so push in reverse.
+    text.chars().rev().all(|c| self.un_get_input(Input::Character(c)))
+  }
+
+  /// Reads a line of input at the given location, only accepting characters
+  /// that pass the `valid` check.
+  ///
+  /// Up to `max` characters can be entered. Rejected characters (and typing
+  /// past `max`) cause a `beep` instead of being shown. Backspace removes the
+  /// last character and Enter finishes the line. If `get_input` gives back
+  /// `None` (such as from a timeout) before Enter is pressed you get `None`.
+  ///
+  /// This draws the accepted characters itself, so echo is turned off while
+  /// reading and then put back the way it was before returning.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// // Pushed input comes back out in reverse order.
+  /// for &c in ['\n', '3', '\u{7f}', '2', 'x', '1'].iter() {
+  ///     easy.un_get_input(Input::Character(c));
+  /// }
+  /// let line = easy.read_validated(0, 0, 10, |c| c.is_ascii_digit());
+  /// assert_eq!(line, Some("13".to_string()));
+  /// ```
+  pub fn read_validated<F: Fn(char) -> bool>(&mut self, row: i32, col: i32, max: usize, valid: F) -> Option<String> {
+    self.read_line_with(row, col, max, valid, None)
+  }
+
+  /// Reads a line of input at the given location like `read_validated`
+  /// (accepting any character), but shows a `*` for each character typed
+  /// instead of the character itself.
+  ///
+  /// Backspace erases the last mask character from the screen as well as the
+  /// last character of the line. This is done by drawing over it directly,
+  /// rather than trusting the terminal's own erase handling, which doesn't
+  /// always clear the screen in cooked mode.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// // Pushed input comes back out in reverse order.
+  /// for &c in ['\n', '\u{8}', 'c', '\u{7f}', 'b', 'a'].iter() {
+  ///     easy.un_get_input(Input::Character(c));
+  /// }
+  /// let password = easy.read_password(0, 0, 10);
+  /// assert_eq!(password, Some("a".to_string()));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, '*' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, ' ' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 2) & 0xFF, ' ' as u32);
+  /// ```
+  pub fn read_password(&mut self, row: i32, col: i32, max: usize) -> Option<String> {
+    self.read_line_with(row, col, max, |_| true, Some('*'))
+  }
+
+  /// The line reading shared by `read_validated` and `read_password`. Each
+  /// accepted character is drawn as itself, or as `mask` if there is one.
+  fn read_line_with<F: Fn(char) -> bool>(&mut self, row: i32, col: i32, max: usize, valid: F, mask: Option<char>) -> Option<String> {
+    if !self.move_rc(row, col) {
+      return None;
+    }
+    let old_echo = self.echo;
+    self.set_echo(false);
+    let mut line = String::new();
+    let mut count = 0;
+    let result = loop {
+      match self.get_input() {
+        Some(ref input) if is_enter(input) => break Some(line),
+        Some(Input::Character('\u{8}')) | Some(Input::Character('\u{7f}')) | Some(Input::KeyBackspace) => {
+          if line.pop().is_some() {
+            count -= 1;
+            self.move_rc(row, col + count as i32);
+            self.print_char(' ');
+            self.move_rc(row, col + count as i32);
+          } else {
+            self.beep();
+          }
+        }
+        Some(Input::Character(c)) => {
+          if count < max && valid(c) {
+            line.push(c);
+            count += 1;
+            self.print_char(mask.unwrap_or(c));
+          } else {
+            self.beep();
+          }
+        }
+        Some(_) => (),
+        None => break None,
+      }
+    };
+    self.set_echo(old_echo);
+    result
+  }
+
+  /// Saves the characters, color pairs, and attributes of every cell in the
+  /// rectangle, so that they can be put back later with `restore_region`.
+  /// This is what lets a popup be drawn over the screen and then taken away
+  /// again. Any part of the rectangle that's off the edge of the window isn't
+  /// saved. The cursor is left where it was.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Rect};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.print("abcdef");
+  /// let snapshot = easy.capture_region(Rect::new(0, 1, 1, 3));
+  /// easy.move_rc(0, 0);
+  /// easy.print("XXXXXX");
+  /// easy.move_rc(5, 5);
+  /// assert!(easy.restore_region(&snapshot));
+  /// assert_eq!(easy.get_cursor_rc(), (5, 5));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 'X' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 1) & 0xFF, 'b' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 3) & 0xFF, 'd' as u32);
+  /// assert_eq!(easy.win.mvinch(0, 4) & 0xFF, 'X' as u32);
+  /// let everything = easy.capture_region(Rect::new(0, 0, i32::MAX, i32::MAX));
+  /// assert!(easy.restore_region(&everything));
+  /// ```
+  pub fn capture_region(&mut self, rect: Rect) -> RegionSnapshot {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let area = rect.intersection(Rect::new(0, 0, row_count, col_count));
+    let mut cells = Vec::new();
+    for row in area.row..area.row + area.row_count {
+      for col in area.col..area.col + area.col_count {
+        cells.push((row, col, self.win.mvinch(row, col)));
+      }
+    }
+    // mvinch moves the cursor as it goes.
+    self.move_rc(old_row, old_col);
+    RegionSnapshot { rect, cells }
+  }
+
+  /// Puts back the cells saved by `capture_region`, exactly as they were. The
+  /// current color pair and attributes don't affect the restored cells, and
+  /// the cursor is put back where it was afterward.
+  pub fn restore_region(&mut self, snapshot: &RegionSnapshot) -> bool {
+    let (old_row, old_col) = self.get_cursor_rc();
+    let (old_attrs, old_pair) = self.win.attrget();
+    self.win.attrset(pancurses::A_NORMAL);
+    let mut success = true;
+    for &(row, col, ch) in &snapshot.cells {
+      success &= self.put_char_at(row, col, ch);
+    }
+    self.win.attrset(old_attrs);
+    self.win.color_set(old_pair);
+    self.move_rc(old_row, old_col);
+    success
+  }
+
+  /// Shows a modal yes/no prompt in a box in the middle of the window and
+  /// waits for the user's answer.
+  ///
+  /// The "Yes" button starts out focused. Left and Right (or Tab) move the
+  /// focus and Enter picks the focused button, or `y` and `n` pick an answer
+  /// directly. Escape counts as "No". While the prompt is up, input waits
+  /// without a timeout, and the previous timeout is restored afterward. Once
+  /// an answer is picked, whatever was under the box is put back.
+  ///
+  /// If input fails entirely you get `false`.
+  ///
+  /// ```rust
+  /// use easycurses::{EasyCurses, Input};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// easy.print("underneath");
+  /// // Input is read back last in first out: Tab, then Enter.
+  /// easy.un_get_input(Input::Character('\n'));
+  /// easy.un_get_input(Input::Character('\t'));
+  /// assert!(!easy.confirm("Are you sure?"));
+  /// easy.un_get_input(Input::Character('y'));
+  /// assert!(easy.confirm("Are you sure?"));
+  /// assert_eq!(easy.win.mvinch(0, 0) & 0xFF, 'u' as u32);
+  /// ```
+  pub fn confirm(&mut self, message: &str) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    let width = (measure_text(message) + 4).max(20).min(col_count);
+    let height = 5.min(row_count);
+    let rect = Rect::new((row_count - height) / 2, (col_count - width) / 2, height, width);
+
+    let (old_row, old_col) = self.get_cursor_rc();
+    let old_timeout = self.input_timeout;
+    let saved = self.capture_region(rect);
+
+    self.set_input_timeout(TimeoutMode::Never);
+    let mut yes_focused = true;
+    let answer = loop {
+      self.fill_region(rect, ' ');
+      self.draw_titled_box(rect, "Confirm", BorderStyle::Single);
+      self.put_str_clipped(rect.row + 1, rect.col + 2, message, rect.col + rect.col_count - 2);
+      let buttons = if yes_focused { "[Yes]   No " } else { " Yes   [No]" };
+      let buttons_col = rect.col + (rect.col_count - buttons.len() as i32) / 2;
+      self.put_str_clipped(rect.row + 3, buttons_col, buttons, rect.col + rect.col_count - 1);
+      self.refresh();
+      match self.get_event() {
+        Some(event) => match confirm_step(yes_focused, event) {
+          ConfirmStep::Focus(yes) => yes_focused = yes,
+          ConfirmStep::Choose(yes) => break yes,
+          ConfirmStep::Ignore => (),
+        },
+        None => break false,
+      }
+    };
+    self.set_input_timeout(old_timeout);
+
+    self.restore_region(&saved);
+    self.move_rc(old_row, old_col);
+    self.refresh();
+    answer
+  }
+
+  /// Sets the window to use the number of lines and columns specified. If you
+  /// pass zero for both then this will make the window's data structures
+  /// attempt to match the current size of the window. This is done
+  /// automatically for you when `KeyResize` comes in through the input
+  /// buffer.
+  ///
+  /// If the window shrinks out from under the cursor, the cursor is moved to
+  /// the nearest spot that's still inside the window (see `clamp_cursor`).
+  /// Otherwise later printing would have nowhere to go and silently vanish.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.resize(24, 80);
+  /// easy.move_rc(20, 70);
+  /// assert!(easy.resize(10, 20));
+  /// assert_eq!(easy.get_cursor_rc(), (9, 19));
+  /// ```
+  pub fn resize(&mut self, new_lines: i32, new_cols: i32) -> bool {
+    // The terminal has probably changed size, so ask the OS again next time.
+    self.os_size_cache = None;
+    let success = to_bool(pancurses::resize_term(new_lines, new_cols));
+    if success {
+      self.clamp_cursor();
+    }
+    success
+  }
+
+  /// Resizes the window just like `resize`, and then writes back whatever was
+  /// on screen beforehand, as much of it as still fits. This gives a cleaner
+  /// result for programs that don't have a routine to redraw everything.
   ///
-  /// The default mode is inherited from the terminal that started the program
-  /// (usually Cooked), so you should _always_ set the desired mode explicitly
-  /// at the start of your program.
+  /// Cells are saved with `capture_region` rather than `dump_styled`, so that
+  /// line drawing characters and the exact color pairs come back unchanged.
+  /// If the resize fails nothing is written and you get `false`.
   ///
-  /// See also the [Input
-  /// Mode](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/intov.html#tag_001_005_002)
-  /// section of the curses documentation.
-  pub fn set_input_mode(&mut self, mode: InputMode) -> bool {
-    to_bool(match mode {
-      InputMode::Character => pancurses::cbreak(),
-      InputMode::Cooked => pancurses::nocbreak(),
-      InputMode::RawCharacter => pancurses::raw(),
-      InputMode::RawCooked => pancurses::noraw(),
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.resize(24, 80);
+  /// easy.move_rc(1, 2);
+  /// easy.print("kept");
+  /// easy.move_rc(20, 70);
+  /// easy.print("gone");
+  /// assert!(easy.resize_preserving(10, 20));
+  /// assert_eq!(easy.win.mvinch(1, 2) & 0xFF, 'k' as u32);
+  /// assert_eq!(easy.win.mvinch(1, 5) & 0xFF, 't' as u32);
+  /// ```
+  pub fn resize_preserving(&mut self, new_lines: i32, new_cols: i32) -> bool {
+    let (row_count, col_count) = self.get_row_col_count();
+    let mut snapshot = self.capture_region(Rect::new(0, 0, row_count, col_count));
+    if !self.resize(new_lines, new_cols) {
+      return false;
+    }
+    let (row_count, col_count) = self.get_row_col_count();
+    let window = Rect::new(0, 0, row_count, col_count);
+    snapshot.cells.retain(|&(row, col, _)| window.contains(row, col));
+    self.restore_region(&snapshot)
+  }
+
+  /// Moves the cursor to the nearest spot inside the window, if it isn't
+  /// inside already, and gives back the resulting `(R,C)` position. `resize`
+  /// does this for you.
+  pub fn clamp_cursor(&mut self) -> (i32, i32) {
+    let (row, col) = self.get_cursor_rc();
+    let (row_count, col_count) = self.get_row_col_count();
+    let clamped = (row.min(row_count - 1).max(0), col.min(col_count - 1).max(0));
+    if clamped != (row, col) {
+      self.move_rc(clamped.0, clamped.1);
+    }
+    clamped
+  }
+
+  /// Checks if the window's size (as given by `get_row_col_count`) agrees with
+  /// the size that the operating system reports for the terminal.
+  ///
+  /// After several rapid resizes curses and the physical terminal can drift
+  /// out of sync. If this returns `false` you'll probably want to call
+  /// `resize(0, 0)` again so that curses picks up the real size.
+  ///
+  /// The terminal's size comes from `stty size` on unix. PDCurses on windows
+  /// offers no separate way to ask, so there this always returns `true`. It
+  /// also returns `true` any time the OS query itself fails, since then
+  /// there's no evidence of a mismatch.
+  ///
+  /// Running `stty` takes a while, so the answer from the OS is reused for
+  /// half a second, or until the next `resize`. That makes it fine to call
+  /// this every frame.
+  ///
+  /// ```rust
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.resize(0, 0);
+  /// assert!(easy.dimensions_match_terminal());
+  /// ```
+  pub fn dimensions_match_terminal(&mut self) -> bool {
+    match cached_os_size(&mut self.os_size_cache, Instant::now(), os_terminal_size) {
+      Some(os_size) => os_size == self.get_row_col_count(),
+      None => true,
+    }
+  }
+
+  /// Makes a new `SubWindow` with the given number of rows and columns, with
+  /// its top left corner at the given row and column of this window. Gives
+  /// `None` if the subwindow wouldn't fit entirely within this window.
+  pub fn new_subwindow(&self, rows: i32, cols: i32, start_row: i32, start_col: i32) -> Option<SubWindow> {
+    self.win.subwin(rows, cols, start_row, start_col).ok().map(|win| SubWindow {
+      win,
+      keypad: false,
+      input_timeout: TimeoutMode::Never,
+      lazy_pairs: self.pair_cache.is_some(),
     })
   }
 
-  /// This controls how long `get_input` will wait before returning a `None`
-  /// value.
+  /// Gives a subwindow the same per-window settings as this window: special
+  /// key processing, the input timeout, the current color pair and
+  /// attributes, and the background. A new subwindow starts out with curses'
+  /// defaults instead.
   ///
-  /// The default mode is an unlimited wait.
+  /// Curses keeps those settings separately for each window. Others are
+  /// global and affect every window at once, so there's nothing to copy: the
+  /// input mode, echo, and cursor visibility.
   ///
-  /// The `WaitUpTo` value is measured in milliseconds, and any negative value
-  /// is treated as 0 (the same as an immediate timeout).
+  /// ```rust
+  /// use easycurses::TimeoutMode;
+  /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+  /// easy.set_keypad_enabled(true);
+  /// easy.set_input_timeout(TimeoutMode::Immediate);
+  /// let mut sub = easy.new_subwindow(3, 10, 1, 1).unwrap();
+  /// assert!(!sub.is_keypad_enabled());
+  /// easy.copy_settings_to(&mut sub);
+  /// assert!(sub.is_keypad_enabled());
+  /// assert_eq!(sub.get_input_timeout(), TimeoutMode::Immediate);
+  /// ```
+  pub fn copy_settings_to(&self, child: &mut SubWindow) {
+    child.set_keypad_enabled(self.keypad);
+    child.set_input_timeout(self.input_timeout);
+    let (attrs, pair_id) = self.win.attrget();
+    child.win.attrset(attrs);
+    child.win.color_set(pair_id);
+    child.win.bkgdset(self.win.getbkgd());
+  }
+}
+
+/// How far apart tab stops are for `tab_to` when tab expansion is off.
+const DEFAULT_TAB_WIDTH: i32 = 8;
+
+/// The first multiple of `tab_width` at or after `col`. A `tab_width` of zero
+/// has no tab stops, so that gives `col` itself.
+fn next_tab_stop(col: i32, tab_width: i32) -> i32 {
+  if tab_width > 0 {
+    col + (tab_width - col.rem_euclid(tab_width)) % tab_width
+  } else {
+    col
+  }
+}
+
+/// Replaces each tab with spaces out to the next multiple of `tab_width`,
+/// given the column that the text starts at and the column that each new line
+/// starts at.
+fn expand_tabs(text: &str, start_col: i32, line_start_col: i32, tab_width: u8) -> String {
+  let tab_width = i32::from(tab_width);
+  let mut out = String::with_capacity(text.len());
+  let mut col = start_col;
+  for c in text.chars() {
+    match c {
+      '\t' => {
+        if tab_width > 0 {
+          let spaces = tab_width - col.rem_euclid(tab_width);
+          out.extend((0..spaces).map(|_| ' '));
+          col += spaces;
+        }
+      }
+      '\n' => {
+        out.push(c);
+        col = line_start_col;
+      }
+      _ => {
+        out.push(c);
+        col += 1;
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod expand_tabs_tests {
+  use super::*;
+
+  #[test]
+  fn test_expand_tabs_to_tab_stops() {
+    assert_eq!(expand_tabs("a\tb", 0, 0, 4), "a   b");
+    assert_eq!(expand_tabs("\tb", 0, 0, 4), "    b");
+    assert_eq!(expand_tabs("abcd\tb", 0, 0, 4), "abcd    b");
+    assert_eq!(expand_tabs("a\tb", 2, 0, 4), "a b");
+  }
+
+  #[test]
+  fn test_expand_tabs_new_lines() {
+    assert_eq!(expand_tabs("ab\n\tc", 0, 0, 4), "ab\n    c");
+    assert_eq!(expand_tabs("ab\n\tc", 0, 2, 4), "ab\n  c");
+  }
+
+  #[test]
+  fn test_expand_tabs_zero_width() {
+    assert_eq!(expand_tabs("a\tb", 0, 0, 0), "ab");
+  }
+
+  #[test]
+  fn test_next_tab_stop() {
+    assert_eq!(next_tab_stop(0, 8), 0);
+    assert_eq!(next_tab_stop(1, 8), 8);
+    assert_eq!(next_tab_stop(8, 8), 8);
+    assert_eq!(next_tab_stop(9, 4), 12);
+    assert_eq!(next_tab_stop(5, 0), 5);
+  }
+}
+
+/// Turns an `Input` back into the key code that curses uses for it. Characters
+/// give their Unicode code point, and `Input::Unknown` gives the code that it
+/// carries.
+fn input_to_key_code(input: Input) -> Option<i32> {
+  match input {
+    Input::Character(c) => Some(c as i32),
+    Input::Unknown(code) => Some(code),
+    #[cfg(windows)]
+    Input::KeyResize => Some(pancurses::KEY_RESIZE),
+    #[cfg(windows)]
+    Input::KeyMouse => Some(pancurses::KEY_MOUSE),
+    special => pancurses::SPECIAL_KEY_CODES.iter().position(|&key| key == special).map(|index| {
+      // This is the reverse of how pancurses looks up special keys, which
+      // skips over the function keys past F15.
+      let code = index as i32 + pancurses::KEY_OFFSET;
+      if code <= pancurses::KEY_F15 {
+        code
+      } else {
+        code + 48
+      }
+    }),
+  }
+}
+
+#[cfg(test)]
+mod key_code_tests {
+  use super::*;
+
+  #[test]
+  fn test_input_to_key_code_characters() {
+    assert_eq!(input_to_key_code(Input::Character('a')), Some(97));
+    assert_eq!(input_to_key_code(Input::Character('\u{e9}')), Some(0xe9));
+    assert_eq!(input_to_key_code(Input::Unknown(12345)), Some(12345));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_input_to_key_code_special_keys() {
+    // These are the values from ncurses' curses.h
+    assert_eq!(input_to_key_code(Input::KeyDown), Some(0o402));
+    assert_eq!(input_to_key_code(Input::KeyF1), Some(0o411));
+    assert_eq!(input_to_key_code(Input::KeyDL), Some(0o510));
+    assert_eq!(input_to_key_code(Input::KeyResize), Some(0o632));
+  }
+}
+
+/// Converts a duration to the whole milliseconds that curses timeouts use,
+/// saturating at `i32::MAX`.
+fn duration_to_timeout_ms(duration: Duration) -> i32 {
+  let ms = duration.as_secs().saturating_mul(1000).saturating_add(u64::from(duration.subsec_millis()));
+  ms.min(i32::MAX as u64) as i32
+}
+
+#[cfg(test)]
+mod timeout_ms_tests {
+  use super::*;
+
+  #[test]
+  fn test_duration_to_timeout_ms() {
+    assert_eq!(duration_to_timeout_ms(Duration::new(0, 0)), 0);
+    assert_eq!(duration_to_timeout_ms(Duration::from_millis(1500)), 1500);
+    assert_eq!(duration_to_timeout_ms(Duration::new(0, 999_999)), 0);
+  }
+
+  #[test]
+  fn test_duration_to_timeout_ms_saturates() {
+    assert_eq!(duration_to_timeout_ms(Duration::from_secs(1 << 40)), i32::MAX);
+    assert_eq!(duration_to_timeout_ms(Duration::new(u64::MAX, 0)), i32::MAX);
+  }
+}
+
+/// Records that `key` was seen at `now`, and says if it was also seen within
+/// `window_ms` milliseconds before that.
+fn key_repeats(last_seen: &mut HashMap<Input, Instant>, key: Input, now: Instant, window_ms: i32) -> bool {
+  let window = Duration::from_millis(window_ms.max(0) as u64);
+  match last_seen.insert(key, now) {
+    Some(before) => now >= before && now - before <= window,
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod key_repeat_tests {
+  use super::*;
+
+  #[test]
+  fn test_key_repeats() {
+    let mut last_seen = HashMap::new();
+    let start = Instant::now();
+    let at = |ms| start + Duration::from_millis(ms);
+    let left = Input::KeyLeft;
+    assert!(!key_repeats(&mut last_seen, left, at(0), 100));
+    assert!(key_repeats(&mut last_seen, left, at(50), 100));
+    assert!(key_repeats(&mut last_seen, left, at(150), 100));
+    // A different key doesn't count, and doesn't disturb the first one.
+    assert!(!key_repeats(&mut last_seen, Input::KeyRight, at(160), 100));
+    assert!(!key_repeats(&mut last_seen, left, at(400), 100));
+    assert!(!key_repeats(&mut last_seen, left, at(401), -5));
+  }
+}
+
+/// Wraps text in the OSC 8 escape sequences that make it a hyperlink. OSC 8
+/// only allows the printable ASCII bytes 0x20 to 0x7E in a URL, so any other
+/// byte gives `None`, since something like an ESC or BEL would end the
+/// sequence early. Control characters are dropped from the text, see
+/// `link_text`.
+fn osc8_link(text: &str, url: &str) -> Option<String> {
+  if !url.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+    return None;
+  }
+  Some(format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, link_text(text)))
+}
+
+/// The text of a link with every control character removed, so that the text
+/// can't end the link sequence or start a new escape sequence of its own.
+fn link_text(text: &str) -> String {
+  text.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod link_tests {
+  use super::*;
+
+  #[test]
+  fn test_osc8_link() {
+    let link = osc8_link("docs", "https://docs.rs/easycurses").unwrap();
+    assert_eq!(link, "\x1b]8;;https://docs.rs/easycurses\x1b\\docs\x1b]8;;\x1b\\");
+    assert!(link.contains("https://docs.rs/easycurses"));
+    assert!(link.contains("docs"));
+  }
+
+  #[test]
+  fn test_osc8_link_rejects_unsafe_url() {
+    assert_eq!(osc8_link("docs", "https://x\x1b]0;pwned\x07"), None);
+    assert_eq!(osc8_link("docs", "https://x\x07"), None);
+    assert_eq!(osc8_link("docs", "https://x/\u{e9}"), None);
+    assert_eq!(osc8_link("docs", "https://x/a b"), Some("\x1b]8;;https://x/a b\x1b\\docs\x1b]8;;\x1b\\".to_string()));
+  }
+
+  #[test]
+  fn test_osc8_link_strips_text_controls() {
+    let link = osc8_link("do\x1b[2Jcs\u{9b}\n", "https://x").unwrap();
+    assert_eq!(link, "\x1b]8;;https://x\x1b\\do[2Jcs\x1b]8;;\x1b\\");
+  }
+}
+
+/// What a single event does to a `confirm` prompt.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ConfirmStep {
+  /// Move the focus, `true` being the "Yes" button.
+  Focus(bool),
+  /// Close the prompt with this answer.
+  Choose(bool),
+  /// The event doesn't do anything.
+  Ignore,
+}
+
+/// Works out what an event does to a `confirm` prompt, given which button
+/// currently has the focus.
+fn confirm_step(yes_focused: bool, event: Event) -> ConfirmStep {
+  match event {
+    Event::Direction(Direction::Left) => ConfirmStep::Focus(true),
+    Event::Direction(Direction::Right) => ConfirmStep::Focus(false),
+    Event::Character('\t') => ConfirmStep::Focus(!yes_focused),
+    Event::Enter => ConfirmStep::Choose(yes_focused),
+    Event::Character('y') | Event::Character('Y') => ConfirmStep::Choose(true),
+    Event::Character('n') | Event::Character('N') | Event::Character('\u{1b}') => ConfirmStep::Choose(false),
+    _ => ConfirmStep::Ignore,
+  }
+}
+
+#[cfg(test)]
+mod confirm_tests {
+  use super::*;
+
+  #[test]
+  fn test_confirm_step_focus() {
+    assert_eq!(confirm_step(true, Direction::Right.into()), ConfirmStep::Focus(false));
+    assert_eq!(confirm_step(false, Direction::Left.into()), ConfirmStep::Focus(true));
+    assert_eq!(confirm_step(true, '\t'.into()), ConfirmStep::Focus(false));
+    assert_eq!(confirm_step(false, '\t'.into()), ConfirmStep::Focus(true));
+  }
+
+  #[test]
+  fn test_confirm_step_choose() {
+    assert_eq!(confirm_step(true, '\n'.into()), ConfirmStep::Choose(true));
+    assert_eq!(confirm_step(false, '\r'.into()), ConfirmStep::Choose(false));
+    assert_eq!(confirm_step(false, Event::Enter), ConfirmStep::Choose(false));
+    assert_eq!(confirm_step(false, 'y'.into()), ConfirmStep::Choose(true));
+    assert_eq!(confirm_step(true, 'N'.into()), ConfirmStep::Choose(false));
+    assert_eq!(confirm_step(true, '\u{1b}'.into()), ConfirmStep::Choose(false));
+  }
+
+  #[test]
+  fn test_confirm_step_ignore() {
+    assert_eq!(confirm_step(true, 'x'.into()), ConfirmStep::Ignore);
+    assert_eq!(confirm_step(true, Direction::Up.into()), ConfirmStep::Ignore);
+    assert_eq!(confirm_step(true, Event::Resize), ConfirmStep::Ignore);
+  }
+}
+
+/// Finds the first character index to show in a scrolling field of `width`
+/// cells so that the character at `cursor_pos` stays visible.
+fn scrolling_field_start(width: usize, cursor_pos: usize) -> usize {
+  if cursor_pos < width {
+    0
+  } else {
+    cursor_pos + 1 - width
+  }
+}
+
+#[cfg(test)]
+mod scrolling_field_tests {
+  use super::*;
+
+  #[test]
+  fn test_scrolling_field_start_cursor_at_start() {
+    assert_eq!(scrolling_field_start(5, 0), 0);
+  }
+
+  #[test]
+  fn test_scrolling_field_start_cursor_in_middle() {
+    // "abcdefghij" with the caret on 'g' shows "cdefg".
+    assert_eq!(scrolling_field_start(5, 4), 0);
+    assert_eq!(scrolling_field_start(5, 6), 2);
+  }
+
+  #[test]
+  fn test_scrolling_field_start_cursor_at_end() {
+    // Appending to "abcdefghij" shows "ghij" plus the empty caret cell.
+    assert_eq!(scrolling_field_start(5, 10), 6);
+    assert_eq!(scrolling_field_start(1, 10), 10);
+  }
+
+  /// The part of `text` that a field `width` cells wide shows.
+  fn visible(text: &str, width: usize, cursor_pos: usize) -> String {
+    text.chars().skip(scrolling_field_start(width, cursor_pos)).take(width).collect()
+  }
+
+  #[test]
+  fn test_scrolling_field_too_long_text() {
+    let text = "The quick brown fox";
+    assert_eq!(visible(text, 8, 0), "The quic");
+    assert_eq!(visible(text, 8, 10), " quick b");
+    assert_eq!(visible(text, 8, 18), "rown fox");
+    assert_eq!(visible(text, 8, 19), "own fox");
+  }
+}
+
+/// Keeps track of frame timing for `EasyCurses::run_loop`.
+#[derive(Debug)]
+struct FrameTimer {
+  target: Duration,
+  frame_start: Option<Instant>,
+}
+
+impl FrameTimer {
+  /// A timer aiming for the given frames per second. Zero means that frames
+  /// are never held back.
+  fn new(fps: u32) -> Self {
+    FrameTimer {
+      target: if fps == 0 { Duration::new(0, 0) } else { Duration::new(1, 0) / fps },
+      frame_start: None,
+    }
+  }
+
+  /// Marks the start of a new frame, giving back the time since the start of
+  /// the previous frame (zero for the very first frame).
+  fn start_frame(&mut self, now: Instant) -> Duration {
+    let delta = self.frame_start.map(|prev| now.duration_since(prev)).unwrap_or_default();
+    self.frame_start = Some(now);
+    delta
+  }
+
+  /// How much time is left in the current frame.
+  fn remaining(&self, now: Instant) -> Duration {
+    match self.frame_start {
+      Some(start) => self.target.checked_sub(now.duration_since(start)).unwrap_or_default(),
+      None => Duration::new(0, 0),
+    }
+  }
+}
+
+#[cfg(test)]
+mod frame_timer_tests {
+  use super::*;
+
+  #[test]
+  fn test_frame_timer_delta_and_remaining() {
+    let base = Instant::now();
+    let ms = Duration::from_millis;
+    let mut timer = FrameTimer::new(50);
+    assert_eq!(timer.target, ms(20));
+    assert_eq!(timer.start_frame(base), ms(0));
+    assert_eq!(timer.remaining(base + ms(5)), ms(15));
+    // The frame ran long, so there's no time left to sleep.
+    assert_eq!(timer.remaining(base + ms(25)), ms(0));
+    assert_eq!(timer.start_frame(base + ms(25)), ms(25));
+    assert_eq!(timer.start_frame(base + ms(45)), ms(20));
+  }
+
+  #[test]
+  fn test_frame_timer_zero_fps() {
+    let base = Instant::now();
+    let mut timer = FrameTimer::new(0);
+    timer.start_frame(base);
+    assert_eq!(timer.remaining(base), Duration::new(0, 0));
+  }
+}
+
+/// The saved contents of a rectangle of the window, from
+/// [`EasyCurses::capture_region`].
+///
+/// [`EasyCurses::capture_region`]: struct.EasyCurses.html#method.capture_region
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionSnapshot {
+  rect: Rect,
+  cells: Vec<(i32, i32, pancurses::chtype)>,
+}
+
+impl RegionSnapshot {
+  /// The rectangle that was captured.
+  pub fn rect(&self) -> Rect {
+    self.rect
+  }
+}
+
+/// Something that text can be drawn onto.
+///
+/// This is implemented by `EasyCurses`, `SubWindow`, and [`StringGrid`], an
+/// in-memory stand-in for a terminal. Layout helpers such as
+/// `print_centered` are written against this trait, so they can be tested
+/// against a `StringGrid` with no terminal around at all.
+///
+/// [`StringGrid`]: struct.StringGrid.html
+pub trait Drawable {
+  /// The number of rows and columns available, see
+  /// `EasyCurses::get_row_col_count`.
+  fn get_row_col_count(&self) -> (i32, i32);
+
+  /// Moves the cursor to the given `(R,C)` position, see
+  /// `EasyCurses::move_rc`.
+  fn move_rc(&mut self, row: i32, col: i32) -> bool;
+
+  /// Prints a string at the cursor, see `EasyCurses::print`.
+  fn print_str(&mut self, text: &str) -> bool;
+
+  /// Prints the text centered within the given row, measuring it with
+  /// `measure_text`. Text that's too wide is started at the left edge and left
+  /// to wrap.
   ///
-  /// See also: The
-  /// [notimeout](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/notimeout.html)
-  /// curses function.
-  pub fn set_input_timeout(&mut self, mode: TimeoutMode) {
-    match mode {
-      TimeoutMode::Immediate => self.win.timeout(0),
-      TimeoutMode::WaitUpTo(n) => self.win.timeout(n.max(0)),
-      TimeoutMode::Never => self.win.timeout(-1),
-    };
+  /// ```rust
+  /// use easycurses::{Drawable, EasyCurses};
+  /// let mut easy = EasyCurses::initialize_system().unwrap();
+  /// assert!(easy.print_centered(0, "Title"));
+  /// ```
+  fn print_centered(&mut self, row: i32, text: &str) -> bool {
+    let (_, col_count) = self.get_row_col_count();
+    let width = measure_text(text);
+    let col = ((col_count - width) / 2).max(0);
+    self.move_rc(row, col) && self.print_str(text)
+  }
+}
+
+impl Drawable for EasyCurses {
+  fn get_row_col_count(&self) -> (i32, i32) {
+    EasyCurses::get_row_col_count(self)
+  }
+
+  fn move_rc(&mut self, row: i32, col: i32) -> bool {
+    EasyCurses::move_rc(self, row, col)
+  }
+
+  fn print_str(&mut self, text: &str) -> bool {
+    self.print(text)
+  }
+}
+
+/// A grid of characters in memory that acts like a small terminal, for
+/// testing drawing code without curses.
+///
+/// Printing works like it does with curses: the text goes at the cursor,
+/// wraps at the right edge, and `'\n'` moves to the start of the next line.
+/// Running off the bottom of the grid is an error.
+///
+/// ```rust
+/// use easycurses::{Drawable, StringGrid};
+/// let mut grid = StringGrid::new(2, 5);
+/// assert!(grid.print_str("abcdefg"));
+/// assert_eq!(grid.lines(), vec!["abcde".to_string(), "fg   ".to_string()]);
+/// assert_eq!(grid.get_cursor_rc(), (1, 2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringGrid {
+  cells: Vec<Vec<char>>,
+  cursor: (i32, i32),
+}
+
+impl StringGrid {
+  /// Makes a grid of the given size, filled with spaces and with the cursor
+  /// in the top left.
+  pub fn new(row_count: i32, col_count: i32) -> Self {
+    StringGrid {
+      cells: vec![vec![' '; col_count.max(0) as usize]; row_count.max(0) as usize],
+      cursor: (0, 0),
+    }
+  }
+
+  /// Gives the cursor's current `(R,C)` position.
+  pub fn get_cursor_rc(&self) -> (i32, i32) {
+    self.cursor
+  }
+
+  /// Gives the contents of each row as a string.
+  pub fn lines(&self) -> Vec<String> {
+    self.cells.iter().map(|row| row.iter().collect()).collect()
+  }
+
+  /// Puts one character at the cursor and advances the cursor.
+  fn put_char(&mut self, c: char) -> bool {
+    let (row_count, col_count) = Drawable::get_row_col_count(self);
+    let (row, col) = self.cursor;
+    if row >= row_count || col_count == 0 {
+      return false;
+    }
+    if c == '\n' {
+      self.cursor = (row + 1, 0);
+    } else {
+      self.cells[row as usize][col as usize] = c;
+      self.cursor = if col + 1 < col_count { (row, col + 1) } else { (row + 1, 0) };
+    }
+    true
+  }
+}
+
+impl Drawable for StringGrid {
+  fn get_row_col_count(&self) -> (i32, i32) {
+    (self.cells.len() as i32, self.cells.first().map_or(0, |row| row.len() as i32))
+  }
+
+  fn move_rc(&mut self, row: i32, col: i32) -> bool {
+    let (row_count, col_count) = Drawable::get_row_col_count(self);
+    if Rect::new(0, 0, row_count, col_count).contains(row, col) {
+      self.cursor = (row, col);
+      true
+    } else {
+      false
+    }
+  }
+
+  fn print_str(&mut self, text: &str) -> bool {
+    text.chars().all(|c| self.put_char(c))
+  }
+}
+
+#[cfg(test)]
+mod drawable_tests {
+  use super::*;
+
+  #[test]
+  fn test_print_centered() {
+    let mut grid = StringGrid::new(2, 10);
+    assert!(grid.print_centered(1, "abcd"));
+    assert_eq!(grid.lines(), vec!["          ".to_string(), "   abcd   ".to_string()]);
   }
 
-  /// Enables special key processing from buttons such as the keypad and arrow
-  /// keys. This defaults to `false`. You probably want to set it to `true`.
-  /// If it's not on and the user presses a special key then get_key will
-  /// return will do nothing or give `ERR`.
-  pub fn set_keypad_enabled(&mut self, use_keypad: bool) -> bool {
-    to_bool(self.win.keypad(use_keypad))
+  #[test]
+  fn test_print_centered_odd_leftover() {
+    let mut grid = StringGrid::new(1, 6);
+    assert!(grid.print_centered(0, "abc"));
+    assert_eq!(grid.lines(), vec![" abc  ".to_string()]);
   }
 
-  /// Enables or disables the automatic echoing of input into the window as
-  /// the user types. Default to on, but you probably want it to be off most
-  /// of the time.
-  pub fn set_echo(&mut self, echoing: bool) -> bool {
-    to_bool(if echoing { pancurses::echo() } else { pancurses::noecho() })
+  #[test]
+  fn test_print_centered_too_wide() {
+    let mut grid = StringGrid::new(2, 4);
+    assert!(grid.print_centered(0, "abcdef"));
+    assert_eq!(grid.lines(), vec!["abcd".to_string(), "ef  ".to_string()]);
   }
 
-  // TODO: pancurses::resize_term?
+  #[test]
+  fn test_string_grid_bounds() {
+    let mut grid = StringGrid::new(1, 3);
+    assert!(!grid.move_rc(1, 0));
+    assert!(!grid.move_rc(0, 3));
+    assert!(!grid.print_str("abcd"));
+    assert_eq!(grid.lines(), vec!["abc".to_string()]);
+  }
 
-  /// Checks if the current terminal supports the use of colors.
-  pub fn is_color_terminal(&self) -> bool {
-    self.color_support
+  #[test]
+  fn test_string_grid_no_columns() {
+    let mut grid = StringGrid::new(2, 0);
+    assert!(!grid.print_str("a"));
+    assert_eq!(grid.lines(), vec![String::new(), String::new()]);
   }
+}
 
-  /// Sets the current color pair of the window. Output at any location will
-  /// use this pair until a new pair is set. Does nothing if the terminal does
-  /// not support colors in the first place.
-  pub fn set_color_pair(&mut self, pair: ColorPair) {
-    if self.color_support {
-      self.win.color_set(pair.0);
+/// What a [`Widget`] did with an event that it was given.
+///
+/// [`Widget`]: trait.Widget.html
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WidgetResult {
+  /// The widget had no use for the event, so it can go elsewhere.
+  Ignored,
+  /// The widget used the event.
+  Consumed,
+  /// The widget used the event and is done, giving back a value, such as
+  /// the chosen item of a menu or the text of an input box.
+  Submit(String),
+}
+
+/// A piece of user interface that can draw itself and react to events.
+///
+/// Menus, status bars, dialogs and so on can all implement this, so that a
+/// program can keep a `Vec<Box<dyn Widget>>` and treat them all the same way.
+/// See [`dispatch_event`] for handing an event to a list of widgets.
+///
+/// [`dispatch_event`]: fn.dispatch_event.html
+pub trait Widget {
+  /// Draws the widget.
+  fn draw(&self, easy: &mut EasyCurses);
+
+  /// Updates the widget in response to an event, and says what it did.
+  fn handle_event(&mut self, event: Event) -> WidgetResult;
+}
+
+/// Offers an event to each widget in turn, stopping at the first one that
+/// doesn't ignore it, and gives back that widget's result. If every widget
+/// ignores the event you get `WidgetResult::Ignored`.
+pub fn dispatch_event(widgets: &mut [Box<dyn Widget>], event: Event) -> WidgetResult {
+  for widget in widgets.iter_mut() {
+    match widget.handle_event(event) {
+      WidgetResult::Ignored => (),
+      other => return other,
+    }
+  }
+  WidgetResult::Ignored
+}
+
+#[cfg(test)]
+mod widget_tests {
+  use super::*;
+
+  /// Collects typed characters until Enter is pressed.
+  struct LineInput {
+    text: String,
+  }
+
+  impl Widget for LineInput {
+    fn draw(&self, easy: &mut EasyCurses) {
+      easy.print(&self.text);
+    }
+
+    fn handle_event(&mut self, event: Event) -> WidgetResult {
+      match event {
+        Event::Character(c) => {
+          self.text.push(c);
+          WidgetResult::Consumed
+        }
+        Event::Enter => WidgetResult::Submit(self.text.clone()),
+        _ => WidgetResult::Ignored,
+      }
     }
   }
 
-  /// Enables or disables bold text for all future input.
-  pub fn set_bold(&mut self, bold_on: bool) -> bool {
-    to_bool(if bold_on {
-      self.win.attron(pancurses::Attribute::Bold)
-    } else {
-      self.win.attroff(pancurses::Attribute::Bold)
-    })
+  /// Only cares about arrow keys.
+  struct Arrows {
+    moves: u32,
   }
 
-  /// Enables or disables underlined text for all future input.
-  pub fn set_underline(&mut self, underline_on: bool) -> bool {
-    to_bool(if underline_on {
-      self.win.attron(pancurses::Attribute::Underline)
-    } else {
-      self.win.attroff(pancurses::Attribute::Underline)
-    })
+  impl Widget for Arrows {
+    fn draw(&self, _easy: &mut EasyCurses) {}
+
+    fn handle_event(&mut self, event: Event) -> WidgetResult {
+      match event {
+        Event::Direction(_) => {
+          self.moves += 1;
+          WidgetResult::Consumed
+        }
+        _ => WidgetResult::Ignored,
+      }
+    }
   }
 
-  /// Returns the number of rows and columns available in the window. Each of
-  /// these are the number of locations in that dimension, but the rows and
-  /// cols (as well as the Xs and Ys if you care to use that coordinate space)
-  /// use 0-based indexing, so the actual addressable locations are numbered 0
-  /// to N-1, similar to with slices, `.len()`, and indexing. Fortunately, the
-  /// normal rust Range type already handles this for us. If you wanted to
-  /// iterate every cell of the window you'd probably use a loop like this:
+  #[test]
+  fn test_dispatch_event() {
+    let mut widgets: Vec<Box<dyn Widget>> = vec![Box::new(Arrows { moves: 0 }), Box::new(LineInput { text: String::new() })];
+    assert_eq!(dispatch_event(&mut widgets, Event::Direction(Direction::Up)), WidgetResult::Consumed);
+    assert_eq!(dispatch_event(&mut widgets, Event::Character('h')), WidgetResult::Consumed);
+    assert_eq!(dispatch_event(&mut widgets, Event::Character('i')), WidgetResult::Consumed);
+    assert_eq!(dispatch_event(&mut widgets, Event::Resize), WidgetResult::Ignored);
+    assert_eq!(dispatch_event(&mut widgets, Event::Enter), WidgetResult::Submit("hi".to_string()));
+  }
+}
+
+/// A window that covers part of the main `EasyCurses` window.
+///
+/// A subwindow shares its character cells with the window it was made from,
+/// so anything drawn in the subwindow is also drawn in the parent. Make one
+/// with [`EasyCurses::new_subwindow`].
+///
+/// A subwindow has its own cursor, attributes, and color pair, and supports
+/// the basic printing and cursor movement of `EasyCurses`. It also implements
+/// [`Drawable`], so the layout helpers work on it too. The more involved
+/// output settings of `EasyCurses`, such as margins and tab expansion, only
+/// apply to the main window.
+///
+/// Like `EasyCurses` itself, a `SubWindow` is only meaningful while curses is
+/// on. Drop any subwindows before you drop the `EasyCurses` they came from.
+/// Dropping a subwindow deletes the curses window behind it, but leaves the
+/// text that was drawn there in the parent.
+///
+/// ```rust
+/// use easycurses::{Color, ColorPair, Drawable};
+/// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
+/// let mut sub = easy.new_subwindow(3, 10, 2, 4).unwrap();
+/// if easy.is_color_terminal() {
+///     assert!(sub.set_color_pair(ColorPair::new(Color::Red, Color::Black)));
+/// }
+/// assert!(sub.move_rc(1, 1));
+/// assert!(sub.print("hi"));
+/// assert_eq!(sub.get_cursor_rc(), (1, 3));
+/// assert!(sub.print_centered(2, "ok"));
+/// drop(sub);
+/// assert_eq!(easy.win.mvinch(3, 5) & 0xFF, 'h' as u32);
+/// assert_eq!(easy.win.mvinch(4, 8) & 0xFF, 'o' as u32);
+/// ```
+///
+/// [`EasyCurses::new_subwindow`]: struct.EasyCurses.html#method.new_subwindow
+/// [`Drawable`]: trait.Drawable.html
+#[derive(Debug)]
+pub struct SubWindow {
+  win: pancurses::Window,
+  keypad: bool,
+  input_timeout: TimeoutMode,
+  lazy_pairs: bool,
+}
+
+impl SubWindow {
+  /// Moves the subwindow so that its top left corner is at the given row and
+  /// column of the screen. A subwindow must stay entirely within its parent,
+  /// so a move that would put any part of it out of the parent's bounds fails
+  /// and leaves the subwindow where it was.
+  ///
+  /// The parent window is not redrawn for you, so call `refresh` on the
+  /// parent afterward to get rid of anything left behind at the old spot.
   ///
   /// ```rust
   /// let mut easy = easycurses::EasyCurses::initialize_system().unwrap();
-  /// let (row_count,col_count) = easy.get_row_col_count();
-  /// // using RC coordinates.
-  /// for row in 0..row_count {
-  ///     for col in 0..col_count {
-  ///         easy.move_rc(row,col);
-  ///         let (actual_row,actual_col) = easy.get_cursor_rc();
-  ///         assert!(actual_row == row && actual_col == col);
-  ///     }
-  /// }
-  /// // using XY coordinates.
-  /// for y in 0..row_count {
-  ///     for x in 0..col_count {
-  ///         easy.move_xy(x,y);
-  ///         let (actual_x,actual_y) = easy.get_cursor_xy();
-  ///         assert!(actual_x == x && actual_y == y);
-  ///     }
-  /// }
+  /// let mut sub = easy.new_subwindow(3, 10, 1, 1).unwrap();
+  /// assert_eq!(sub.get_origin(), (1, 1));
+  /// assert!(sub.move_to(4, 6));
+  /// assert_eq!(sub.get_origin(), (4, 6));
+  /// let (row_count, col_count) = easy.get_row_col_count();
+  /// assert!(!sub.move_to(row_count, col_count));
+  /// assert_eq!(sub.get_origin(), (4, 6));
   /// ```
+  pub fn move_to(&mut self, row: i32, col: i32) -> bool {
+    to_bool(self.win.mvwin(row, col))
+  }
+
+  /// Gives the `(row, col)` position of the subwindow's top left corner,
+  /// relative to the top left of the screen.
+  pub fn get_origin(&self) -> (i32, i32) {
+    self.win.get_beg_yx()
+  }
+
+  /// Returns the number of rows and columns in the subwindow.
   pub fn get_row_col_count(&self) -> (i32, i32) {
     self.win.get_max_yx()
   }
 
-  /// Moves the virtual cursor to the row and column specified, relative to
-  /// the top left ("notepad" space). Does not move the terminal's displayed
-  /// cursor (if any) until `refresh` is also called. Out of bounds locations
-  /// cause this command to be ignored.
-  pub fn move_rc(&mut self, row: i32, col: i32) -> bool {
-    to_bool(self.win.mv(row, col))
+  /// Refreshes the subwindow's appearance on the screen.
+  pub fn refresh(&mut self) -> bool {
+    to_bool(self.win.refresh())
   }
 
-  /// Obtains the cursor's current position using `(R,C)` coordinates
-  /// relative to the top left corner.
-  pub fn get_cursor_rc(&self) -> (i32, i32) {
-    self.win.get_cur_yx()
+  /// Turns special key processing on or off for input read through the
+  /// subwindow, see `EasyCurses::set_keypad_enabled`.
+  pub fn set_keypad_enabled(&mut self, use_keypad: bool) -> bool {
+    let success = to_bool(self.win.keypad(use_keypad));
+    if success {
+      self.keypad = use_keypad;
+    }
+    success
   }
 
-  /// Moves the virtual cursor to the x and y specified, relative to the
-  /// bottom left ("cartesian" space). Does not move the terminal's displayed
-  /// cursor (if any) until `refresh` is also called. Out of bounds locations
-  /// cause this command to be ignored.
-  pub fn move_xy(&mut self, x: i32, y: i32) -> bool {
-    let row_count = self.win.get_max_y();
-    to_bool(self.win.mv(row_count - (y + 1), x))
+  /// Checks if special key processing is on, as of the last successful
+  /// `set_keypad_enabled`.
+  pub fn is_keypad_enabled(&self) -> bool {
+    self.keypad
   }
 
-  /// Obtains the cursor's current position using `(X,Y)` coordinates relative
-  /// to the bottom left corner.
-  pub fn get_cursor_xy(&self) -> (i32, i32) {
-    let row_count = self.win.get_max_y();
-    let (row, col) = self.win.get_cur_yx();
-    (col, row_count - (row + 1))
+  /// Sets how long input read through the subwindow waits, see
+  /// `EasyCurses::set_input_timeout`.
+  pub fn set_input_timeout(&mut self, mode: TimeoutMode) {
+    match mode {
+      TimeoutMode::Immediate => self.win.timeout(0),
+      TimeoutMode::WaitUpTo(n) => self.win.timeout(n.max(0)),
+      TimeoutMode::Never => self.win.timeout(-1),
+    };
+    self.input_timeout = mode;
   }
 
-  /// When scrolling is enabled, any attempt to move off the bottom margin
-  /// will cause lines within the scrolling region to scroll up one line. If a
-  /// scrolling region is set but scrolling is not enabled then attempts to go
-  /// off the bottom will just print nothing instead. Use `set_scroll_region`
-  /// to control the size of the scrolling region.
-  pub fn set_scrolling(&mut self, scrolling: bool) -> bool {
-    to_bool(self.win.scrollok(scrolling))
+  /// Gives the timeout that was last set with `set_input_timeout`.
+  pub fn get_input_timeout(&self) -> TimeoutMode {
+    self.input_timeout
   }
 
-  /// Sets the top and bottom margins of the scrolling region. The inputs
-  /// should be the line numbers (relative to the top of the screen) for the
-  /// borders. Either border can be 0.
-  ///
-  /// See also:
-  /// [setscrreg](http://pubs.opengroup.org/onlinepubs/7908799/xcurses/setscrreg.html)
-  pub fn set_scroll_region(&mut self, top: i32, bottom: i32) -> bool {
-    to_bool(self.win.setscrreg(top, bottom))
+  /// Moves the cursor to the given `(R,C)` position within the subwindow,
+  /// see `EasyCurses::move_rc`.
+  pub fn move_rc(&mut self, row: i32, col: i32) -> bool {
+    to_bool(self.win.mv(row, col))
   }
 
-  /// Prints the given string-like value into the window by printing each
-  /// individual character into the window. If there is any error encountered
-  /// upon printing a character, that cancels the printing of the rest of the
-  /// characters.
+  /// Gives the `(R,C)` position of the cursor within the subwindow.
+  pub fn get_cursor_rc(&self) -> (i32, i32) {
+    self.win.get_cur_yx()
+  }
+
+  /// Prints the string at the cursor, wrapping at the right edge of the
+  /// subwindow.
   pub fn print<S: AsRef<str>>(&mut self, asref: S) -> bool {
-    // Here we want to
-    if cfg!(windows) {
-      // PDCurses does an extra intermediate CString allocation, so we just
-      // print out each character one at a time to avoid that.
-      asref.as_ref().chars().all(|c| self.print_char(c))
-    } else {
-      // NCurses, it seems, doesn't do the intermediate allocation and also uses
-      // a faster routine for printing a whole string at once.
-      to_bool(self.win.printw(asref.as_ref()))
-    }
+    to_bool(self.win.addstr(asref.as_ref()))
   }
 
-  /// Prints the given character into the window.
+  /// Prints the character at the cursor, see `EasyCurses::print_char`.
   pub fn print_char<T: ToChtype>(&mut self, character: T) -> bool {
     to_bool(self.win.addch(character))
   }
 
-  /// Inserts the character desired at the current location, pushing the
-  /// current character at that location (and all after it on the same line)
-  /// one cell to the right.
-  pub fn insert_char<T: ToChtype>(&mut self, character: T) -> bool {
-    to_bool(self.win.insch(character))
+  /// Sets the color pair for all future output to the subwindow. Subwindows
+  /// can't set up color pairs on demand, so when the `EasyCurses` they came
+  /// from sets up pairs lazily this gives `false`. Use
+  /// `EasyCurses::copy_settings_to` to pass a color pair along in that case.
+  pub fn set_color_pair(&mut self, pair: ColorPair) -> bool {
+    !self.lazy_pairs && to_bool(self.win.color_set(pair.0))
   }
 
-  /// Deletes the character under the cursor. Characters after it on same the
-  /// line are pulled left one position and the final character cell is left
-  /// blank. The cursor position does not move.
-  pub fn delete_char(&mut self) -> bool {
-    to_bool(self.win.delch())
+  /// Turns an attribute on or off for all future output to the subwindow,
+  /// see `EasyCurses::set_attribute`.
+  pub fn set_attribute(&mut self, attribute: Attribute, on: bool) -> bool {
+    let bits = attribute_to_chtype(attribute);
+    to_bool(if on { self.win.attron(bits) } else { self.win.attroff(bits) })
   }
 
-  /// Inserts a line above the current line. The bottom line is lost.
-  pub fn insert_line(&mut self) -> bool {
-    to_bool(self.win.insertln())
+  /// Clears the subwindow, which also clears that part of the parent.
+  pub fn clear(&mut self) -> bool {
+    to_bool(self.win.erase())
   }
+}
 
-  /// Deletes the line under the cursor. Lines below are moved up one line and
-  /// the final line is left blank. The cursor position does not move.
-  pub fn delete_line(&mut self) -> bool {
-    to_bool(self.win.deleteln())
+impl Drawable for SubWindow {
+  fn get_row_col_count(&self) -> (i32, i32) {
+    SubWindow::get_row_col_count(self)
   }
 
-  /// For positive n, insert n lines into the specified window above the current
-  /// line. The n bottom lines are lost. For negative n, delete n lines
-  /// (starting with the one under the cursor), and move the remaining lines up.
-  /// The bottom n lines are cleared. The current cursor position remains the
-  /// same.
-  pub fn bulk_insert_delete_line(&mut self, n: i32) -> bool {
-    to_bool(self.win.insdelln(n))
+  fn move_rc(&mut self, row: i32, col: i32) -> bool {
+    SubWindow::move_rc(self, row, col)
   }
 
-  /// Clears the entire screen.
+  fn print_str(&mut self, text: &str) -> bool {
+    self.print(text)
+  }
+}
+
+/// A scoped takeover of the terminal by curses.
+///
+/// Creating a `ScreenGuard` turns curses on, and dropping it turns curses back
+/// off so that normal `stdout` printing works again. You can do this as many
+/// times as you like over the life of the program, as long as only one
+/// `ScreenGuard` (or other `EasyCurses` value) is alive at a time. While it's
+/// alive the guard derefs to the `EasyCurses` handle it holds.
+///
+/// ```rust
+/// use easycurses::ScreenGuard;
+///
+/// for round in 0..2 {
+///     {
+///         let mut guard = ScreenGuard::new().unwrap();
+///         // curses is already on, so a second guard can't be made.
+///         assert!(ScreenGuard::new().is_none());
+///         guard.print("Hello from the guard.");
+///         guard.refresh();
+///     }
+///     println!("back to normal output, round {}", round);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ScreenGuard {
+  easy: EasyCurses,
+}
+
+impl ScreenGuard {
+  /// Turns on curses for as long as the guard lives. Follows the same rules as
+  /// [`EasyCurses::initialize_system`], including giving `None` if curses is
+  /// already on.
   ///
-  /// **Note:** This function can cause flickering of the output with PDCurses
-  /// if you're clearing the screen and then immediately writing to the whole
-  /// screen before you end up calling `refresh`. The exact location of the
-  /// flickering effect seems to vary from machine to machine. If you intend
-  /// to simply replace the whole window with new content, just overwrite the
-  /// previous values without calling `clear` and things will be fine.
-  pub fn clear(&mut self) -> bool {
-    to_bool(self.win.clear())
+  /// [`EasyCurses::initialize_system`]: struct.EasyCurses.html#method.initialize_system
+  pub fn new() -> Option<Self> {
+    EasyCurses::initialize_system().map(|easy| ScreenGuard { easy })
   }
+}
 
-  /// Refreshes the window's appearance on the screen. With some
-  /// implementations you don't need to call this, the screen will refresh
-  /// itself on its own. However, for portability, you should call this at the
-  /// end of each draw cycle.
-  pub fn refresh(&mut self) -> bool {
-    to_bool(self.win.refresh())
+impl Deref for ScreenGuard {
+  type Target = EasyCurses;
+
+  fn deref(&self) -> &EasyCurses {
+    &self.easy
   }
+}
 
-  /// Plays an audible beep if possible, if not the screen is flashed. If
-  /// neither is available then nothing happens.
-  pub fn beep(&mut self) {
-    pancurses::beep();
+impl DerefMut for ScreenGuard {
+  fn deref_mut(&mut self) -> &mut EasyCurses {
+    &mut self.easy
   }
+}
 
-  /// Flashes the screen if possible, if not an audible beep is played. If
-  /// neither is available then nothing happens.
-  pub fn flash(&mut self) {
-    pancurses::flash();
+/// A pen for chaining together drawing operations, made with
+/// [`EasyCurses::at`].
+///
+/// Each write picks up where the previous one left off. The pen mutably
+/// borrows the `EasyCurses` it came from, so the handle can't be used for
+/// anything else until the pen is dropped, which is usually at the end of the
+/// statement that made it. Use `done` at the end of a chain to find out if
+/// every step worked.
+///
+/// [`EasyCurses::at`]: struct.EasyCurses.html#method.at
+#[derive(Debug)]
+pub struct CursorPen<'a> {
+  easy: &'a mut EasyCurses,
+  success: bool,
+}
+
+impl<'a> CursorPen<'a> {
+  /// Prints a string, see `EasyCurses::print`.
+  pub fn write<S: AsRef<str>>(mut self, text: S) -> Self {
+    self.success &= self.easy.print(text);
+    self
   }
 
-  /// Gets an `Input` from the curses input buffer. This will block or not
-  /// according to the input mode, see `set_input_mode`. If `KeyResize` is
-  /// seen and `auto_resize` is enabled then the window will automatically
-  /// update its size for you. In that case, `KeyResize` is still passed to
-  /// you so that you can change anything else that might need to be updated.
-  pub fn get_input(&mut self) -> Option<pancurses::Input> {
-    let ret = self.win.getch();
-    if self.auto_resize {
-      match ret {
-        Some(Input::KeyResize) => {
-          self.resize(0, 0);
-        }
-        _ => (),
-      };
-    }
-    ret
+  /// Prints a single character, see `EasyCurses::print_char`.
+  pub fn write_char<T: ToChtype>(mut self, character: T) -> Self {
+    self.success &= self.easy.print_char(character);
+    self
   }
 
-  /// Discards all type-ahead that has been input by the user but not yet read
-  /// by the program.
-  pub fn flush_input(&mut self) {
-    pancurses::flushinp();
+  /// Prints a string in the given color pair, and then goes back to the
+  /// color pair from before.
+  pub fn write_colored<S: AsRef<str>>(mut self, text: S, pair: ColorPair) -> Self {
+    self.success &= self.easy.print_spans(&[(text.as_ref(), pair)]);
+    self
   }
 
-  /// Pushes an `Input` value into the input stack so that it will be returned
-  /// by the next call to `get_input`. The return value is if the operation
-  /// was successful.
-  pub fn un_get_input(&mut self, input: pancurses::Input) -> bool {
-    to_bool(self.win.ungetch(&input))
+  /// Sets the color pair for the rest of the writes, see
+  /// `EasyCurses::set_color_pair`.
+  pub fn color(self, pair: ColorPair) -> Self {
+    self.easy.set_color_pair(pair);
+    self
   }
 
-  /// Sets the window to use the number of lines and columns specified. If you
-  /// pass zero for both then this will make the window's data structures
-  /// attempt to match the current size of the window. This is done
-  /// automatically for you when `KeyResize` comes in through the input
-  /// buffer.
-  pub fn resize(&mut self, new_lines: i32, new_cols: i32) -> bool {
-    to_bool(pancurses::resize_term(new_lines, new_cols))
+  /// Turns bold on or off for the rest of the writes, see
+  /// `EasyCurses::set_bold`.
+  pub fn bold(mut self, bold_on: bool) -> Self {
+    self.success &= self.easy.set_bold(bold_on);
+    self
+  }
+
+  /// Ends the chain, giving back if every step along the way worked.
+  pub fn done(self) -> bool {
+    self.success
   }
 }
 
@@ -744,9 +6742,6 @@ pub fn preserve_panic_message<F: FnOnce(&mut EasyCurses) -> R + UnwindSafe, R>(u
   });
   result.map_err(|e| match e.downcast_ref::<&str>() {
     Some(andstr) => Some(andstr.to_string()),
-    None => match e.downcast_ref::<String>() {
-      Some(string) => Some(string.to_string()),
-      None => None,
-    },
+    None => e.downcast_ref::<String>().map(|string| string.to_string()),
   })
 }